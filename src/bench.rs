@@ -0,0 +1,297 @@
+// Benchmarking of the solutions, without any external crate: every part is run a number of times,
+// and the durations are summarized in a table and optionally written to a JSON file, so that they
+// can be compared between commits.
+use crate::days::RegisteredDay;
+use crate::{catching_panics, print_table, with_silenced_panics, FailedParts};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_ITERATIONS: usize = 10;
+
+pub struct BenchOptions {
+    pub iterations: usize,
+    // Where to write the results as JSON, if anywhere
+    pub json_output: Option<String>,
+}
+
+// Summary of the durations measured over every iteration
+#[derive(Debug, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    // Population standard deviation
+    stddev: Duration,
+}
+impl Stats {
+    fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "Cannot summarize zero samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let secs: Vec<f64> = samples.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+struct Measurements {
+    answer: String,
+    parse: Stats,
+    solve: Stats,
+}
+
+struct PartBenchmark {
+    day: i32,
+    title: &'static str,
+    part: u8,
+    // The description of the failure if the part failed or panicked
+    outcome: Result<Measurements, String>,
+}
+
+// The input file is read once beforehand, so that only the day's own parsing is timed,
+// and every iteration parses the input again before solving the part
+use std::fs;
+fn benchmark_part(
+    registered: &RegisteredDay,
+    part: u8,
+    contents: &str,
+    iterations: usize,
+) -> Result<Measurements, String> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = Vec::with_capacity(iterations);
+    let mut answer = String::new();
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let input = catching_panics(|| Ok(registered.parse(contents)?))?;
+        parse_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        answer = catching_panics(|| registered.solve(part, &input))?;
+        solve_samples.push(start.elapsed());
+    }
+
+    Ok(Measurements {
+        answer,
+        parse: Stats::from_samples(&parse_samples),
+        solve: Stats::from_samples(&solve_samples),
+    })
+}
+
+pub fn run(
+    inputs: &[(&'static RegisteredDay, String)],
+    options: &BenchOptions,
+) -> Result<(), Box<dyn Error>> {
+    println!(
+        "Benchmarking {} iteration(s) of each part",
+        options.iterations
+    );
+
+    let benchmarks: Vec<PartBenchmark> = with_silenced_panics(|| {
+        inputs
+            .iter()
+            .flat_map(|(registered, input_filename)| {
+                let contents = fs::read_to_string(input_filename)
+                    .map_err(|e| format!("cannot read {}: {}", input_filename, e));
+                [1, 2].into_iter().map(move |part| PartBenchmark {
+                    day: registered.day,
+                    title: registered.title,
+                    part,
+                    outcome: contents.clone().and_then(|contents| {
+                        benchmark_part(registered, part, &contents, options.iterations)
+                    }),
+                })
+            })
+            .collect()
+    });
+
+    print_table(&table_rows(&benchmarks));
+
+    if let Some(json_output) = &options.json_output {
+        fs::write(json_output, to_json(&benchmarks, options.iterations))?;
+        println!("Results written to {}", json_output);
+    }
+
+    let num_failed = benchmarks.iter().filter(|b| b.outcome.is_err()).count();
+    if num_failed > 0 {
+        return Err(Box::new(FailedParts(num_failed)));
+    }
+    Ok(())
+}
+
+fn table_rows(benchmarks: &[PartBenchmark]) -> Vec<[String; 8]> {
+    let header = [
+        "Day", "Part", "Phase", "Min", "Median", "Mean", "Std dev", "Status",
+    ]
+    .map(String::from);
+    let mut rows = vec![header];
+
+    for benchmark in benchmarks {
+        let (day, part) = (format!("{:02}", benchmark.day), benchmark.part.to_string());
+        match &benchmark.outcome {
+            Ok(measurements) => {
+                for (phase, stats) in [
+                    ("parse", &measurements.parse),
+                    ("solve", &measurements.solve),
+                ] {
+                    rows.push([
+                        day.clone(),
+                        part.clone(),
+                        String::from(phase),
+                        format!("{:.2?}", stats.min),
+                        format!("{:.2?}", stats.median),
+                        format!("{:.2?}", stats.mean),
+                        format!("{:.2?}", stats.stddev),
+                        String::from("ok"),
+                    ]);
+                }
+            }
+            Err(failure) => {
+                rows.push([
+                    day,
+                    part,
+                    String::from("-"),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    failure.clone(),
+                ]);
+            }
+        }
+    }
+
+    rows
+}
+
+// Durations are written as whole nanoseconds
+fn to_json(benchmarks: &[PartBenchmark], iterations: usize) -> String {
+    let stats_json = |stats: &Stats| {
+        format!(
+            "{{\"min\": {}, \"median\": {}, \"mean\": {}, \"stddev\": {}}}",
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.stddev.as_nanos()
+        )
+    };
+
+    let parts: Vec<String> = benchmarks
+        .iter()
+        .map(|benchmark| {
+            let mut fields = vec![
+                format!("\"day\": {}", benchmark.day),
+                format!("\"title\": {}", json_string(benchmark.title)),
+                format!("\"part\": {}", benchmark.part),
+            ];
+            match &benchmark.outcome {
+                Ok(measurements) => {
+                    fields.push(format!("\"answer\": {}", json_string(&measurements.answer)));
+                    fields.push(format!("\"parse_ns\": {}", stats_json(&measurements.parse)));
+                    fields.push(format!("\"solve_ns\": {}", stats_json(&measurements.solve)));
+                }
+                Err(failure) => fields.push(format!("\"error\": {}", json_string(failure))),
+            }
+            format!("    {{{}}}", fields.join(", "))
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"iterations\": {},\n  \"parts\": [\n{}\n  ]\n}}\n",
+        iterations,
+        parts.join(",\n")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 8, 6]));
+
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        // sqrt((9 + 1 + 1 + 9) / 4) = sqrt(5) ms
+        assert_eq!(2236, stats.stddev.as_micros());
+
+        let stats = Stats::from_samples(&millis(&[3, 1, 2]));
+        assert_eq!(Duration::from_millis(2), stats.median);
+        assert_eq!(Duration::ZERO, Stats::from_samples(&millis(&[7])).stddev);
+    }
+
+    #[test]
+    fn writes_json() {
+        let benchmarks = [
+            PartBenchmark {
+                day: 13,
+                title: "Transparent \"Origami\"",
+                part: 2,
+                outcome: Ok(Measurements {
+                    answer: String::from("#.\n.#\n"),
+                    parse: Stats::from_samples(&millis(&[1])),
+                    solve: Stats::from_samples(&millis(&[2])),
+                }),
+            },
+            PartBenchmark {
+                day: 14,
+                title: "Extended Polymerization",
+                part: 1,
+                outcome: Err(String::from("panicked: oops")),
+            },
+        ];
+
+        assert_eq!(
+            "{
+  \"iterations\": 1,
+  \"parts\": [
+    {\"day\": 13, \"title\": \"Transparent \\\"Origami\\\"\", \"part\": 2, \"answer\": \"#.\\n.#\\n\", \"parse_ns\": {\"min\": 1000000, \"median\": 1000000, \"mean\": 1000000, \"stddev\": 0}, \"solve_ns\": {\"min\": 2000000, \"median\": 2000000, \"mean\": 2000000, \"stddev\": 0}},
+    {\"day\": 14, \"title\": \"Extended Polymerization\", \"part\": 1, \"error\": \"panicked: oops\"}
+  ]
+}
+",
+            to_json(&benchmarks, 1)
+        );
+        assert_eq!("\"tab\\there\\u0001\"", json_string("tab\there\u{1}"));
+    }
+}
@@ -1,28 +1,36 @@
-use super::{Day01, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
 use std::error::Error;
 
+pub struct Day01;
+pub const META: DayMeta = DayMeta {
+    day: 1,
+    title: "Sonar Sweep",
+};
+
 impl DaySolution for Day01 {
-    fn part_1(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let measurements = read_input(input_file);
-        Ok(count_increases(measurements).to_string())
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|i_str| {
+                i_str
+                    .parse::<i32>()
+                    .map_err(|_| ParseError::from(format!("Bad measurement: {}", i_str)))
+            })
+            .collect()
+    }
+
+    fn part_1(&self, measurements: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(count_increases(measurements.clone()).to_string())
     }
 
-    fn part_2(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let measurements = read_input(input_file);
-        Ok(count_sliding_window_increases(measurements, 3).to_string())
+    fn part_2(&self, measurements: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(count_sliding_window_increases(measurements.clone(), 3).to_string())
     }
 }
 
 // Shared
-use std::fs;
-fn read_input(input_file: &String) -> Vec<i32> {
-    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
-
-    contents
-        .lines()
-        .map(|i_str| i_str.parse::<i32>().unwrap())
-        .collect()
-}
 
 // Part 1
 fn count_increases(measurements: Vec<i32>) -> i32 {
@@ -44,10 +52,7 @@ fn count_sliding_window_increases(measurements: Vec<i32>, sliding_window_size: u
     let mut sum_of_measurements: Vec<i32> = Vec::new();
 
     for i in 0..=measurements.len() - sliding_window_size {
-        let mut sum = 0;
-        for j in i..i + sliding_window_size {
-            sum += measurements[j]
-        }
+        let sum = measurements[i..i + sliding_window_size].iter().sum();
         sum_of_measurements.push(sum);
     }
 
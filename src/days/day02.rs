@@ -1,25 +1,38 @@
-use super::{Day02, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
 use std::error::Error;
 
+pub struct Day02;
+pub const META: DayMeta = DayMeta {
+    day: 2,
+    title: "Dive!",
+};
+
 impl DaySolution for Day02 {
-    fn part_1(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
+    type Input = Vec<SubmarineCommand>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|cmd_str| Ok(SubmarineCommand::from_str(cmd_str)?))
+            .collect()
+    }
+
+    fn part_1(&self, commands: &Self::Input) -> Result<String, Box<dyn Error>> {
         let mut submarine = SimpleSubmarine::new();
-        let commands = read_input(input_file);
-        submarine.process_all(commands);
+        submarine.process_all(commands.clone());
         Ok((submarine.horizontal_pos * submarine.depth).to_string())
     }
 
-    fn part_2(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
+    fn part_2(&self, commands: &Self::Input) -> Result<String, Box<dyn Error>> {
         let mut submarine = ComplicatedSubmarine::new();
-        let commands = read_input(input_file);
-        submarine.process_all(commands);
+        submarine.process_all(commands.clone());
         Ok((submarine.horizontal_pos * submarine.depth).to_string())
     }
 }
 
 // Shared
-#[derive(Debug, PartialEq)]
-enum SubmarineCommand {
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubmarineCommand {
     Forward(i32),
     Down(i32),
     Up(i32),
@@ -29,10 +42,14 @@ impl FromStr for SubmarineCommand {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let coords: Vec<&str> = s.split(' ').collect();
+        let (command, dist_str) = s
+            .split_once(' ')
+            .ok_or_else(|| format!("Missing distance in command: {}", s))?;
 
-        let dist_fromstr = coords[1].parse::<i32>().expect("Bad int value for command");
-        match coords[0] {
+        let dist_fromstr = dist_str
+            .parse::<i32>()
+            .map_err(|_| format!("Bad int value for command: {}", s))?;
+        match command {
             "forward" => Ok(SubmarineCommand::Forward(dist_fromstr)),
             "down" => Ok(SubmarineCommand::Down(dist_fromstr)),
             "up" => Ok(SubmarineCommand::Up(dist_fromstr)),
@@ -41,16 +58,6 @@ impl FromStr for SubmarineCommand {
     }
 }
 
-use std::fs;
-fn read_input(input_file: &String) -> Vec<SubmarineCommand> {
-    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
-
-    contents
-        .lines()
-        .map(|cmd_str| SubmarineCommand::from_str(cmd_str).expect("File contains invalid command"))
-        .collect()
-}
-
 trait Submarine {
     fn process(&mut self, command: SubmarineCommand);
     fn process_all(&mut self, commands: Vec<SubmarineCommand>) {
@@ -151,6 +158,9 @@ mod tests {
         assert_eq!(SubmarineCommand::Forward(5), forward_5);
         assert_eq!(SubmarineCommand::Down(8), down_8);
         assert_eq!(SubmarineCommand::Up(3), up_3);
+        assert!(SubmarineCommand::from_str("forward").is_err());
+        assert!(SubmarineCommand::from_str("up x").is_err());
+        assert!(SubmarineCommand::from_str("left 2").is_err());
     }
 
     #[test]
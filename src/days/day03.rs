@@ -1,35 +1,46 @@
-use super::{Day03, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
 use std::error::Error;
 
+pub struct Day03;
+pub const META: DayMeta = DayMeta {
+    day: 3,
+    title: "Binary Diagnostic",
+};
+
 impl DaySolution for Day03 {
-    fn part_1(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let (diagnostic_report, num_of_bits) = read_input(input_file);
+    type Input = (Vec<usize>, usize);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let parsed_input = input
+            .lines()
+            .map(|b_str| {
+                usize::from_str_radix(b_str, 2)
+                    .map_err(|_| ParseError::from(format!("Bad binary number: {}", b_str)))
+            })
+            .collect::<Result<_, _>>()?;
+        let num_of_bits = input
+            .find('\n')
+            .ok_or_else(|| ParseError::from(String::from("Missing end of line")))?;
+
+        Ok((parsed_input, num_of_bits))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (diagnostic_report, num_of_bits) = input;
         let (gamma_rate, epsilon_rate) =
-            find_gamma_and_epsilon_rates(diagnostic_report, num_of_bits);
+            find_gamma_and_epsilon_rates(diagnostic_report.clone(), *num_of_bits);
         Ok((gamma_rate * epsilon_rate).to_string())
     }
 
-    fn part_2(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let (diagnostic_report, num_of_bits) = read_input(input_file);
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (diagnostic_report, num_of_bits) = input;
         let (o2_generator_rating, co2_scrubber_rating) =
-            find_o2_generator_and_co2_scrubber_ratings(diagnostic_report, num_of_bits);
+            find_o2_generator_and_co2_scrubber_ratings(diagnostic_report.clone(), *num_of_bits);
         Ok((o2_generator_rating * co2_scrubber_rating).to_string())
     }
 }
 
 // Shared
-use std::fs;
-fn read_input(input_file: &String) -> (Vec<usize>, usize) {
-    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
-
-    let parsed_input = contents
-        .lines()
-        .map(|b_str| usize::from_str_radix(b_str, 2).unwrap())
-        .collect();
-    let num_of_bits = contents.find('\n').unwrap();
-
-    (parsed_input, num_of_bits)
-}
 
 fn get_bit_at_pos(num: usize, pos: usize) -> usize {
     let mask = 1 << pos;
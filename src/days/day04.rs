@@ -1,9 +1,40 @@
-use super::{Day04, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
 use std::error::Error;
 
+pub struct Day04;
+pub const META: DayMeta = DayMeta {
+    day: 4,
+    title: "Giant Squid",
+};
+
 impl DaySolution for Day04 {
-    fn part_1(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let (call_order, boards) = read_input(input_file);
+    type Input = (Vec<i32>, Vec<BingoBoard>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let mut call_order_str: Vec<&str> = input.split("\n\n").collect();
+        let boards_str: Vec<&str> = call_order_str.split_off(1);
+        let call_order_str = call_order_str[0];
+
+        let call_order: Vec<i32> = call_order_str
+            .trim()
+            .split(",")
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
+
+        let mut boards = Vec::new();
+        for b_str in boards_str {
+            let board_nums: Vec<i32> = b_str
+                .split_ascii_whitespace()
+                .map(parse_number)
+                .collect::<Result<_, _>>()?;
+            boards.push(BingoBoard::new(&board_nums));
+        }
+
+        Ok((call_order, boards))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (call_order, boards) = input.clone();
         let strategy = FirstWinEndsGame::new();
         let mut game = BingoGame::new(call_order, boards, Box::new(strategy));
 
@@ -18,8 +49,8 @@ impl DaySolution for Day04 {
         Err(Box::new(UnwinableBingoGame))
     }
 
-    fn part_2(&self, input_file: &String) -> Result<String, Box<dyn Error>> {
-        let (call_order, boards) = read_input(input_file);
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (call_order, boards) = input.clone();
         let strategy = LastWinEndsGame::new(&boards);
         let mut game = BingoGame::new(call_order, boards, Box::new(strategy));
 
@@ -52,12 +83,12 @@ impl BingoBoardCell {
 
 const NUM_CELLS_IN_BINGO_BOARD: usize = 5 * 5;
 #[derive(Clone, Debug)]
-struct BingoBoard {
+pub struct BingoBoard {
     cells: [BingoBoardCell; NUM_CELLS_IN_BINGO_BOARD],
     bingo: bool,
 }
 impl BingoBoard {
-    fn new(numbers: &Vec<i32>) -> Self {
+    fn new(numbers: &[i32]) -> Self {
         let mut me = BingoBoard {
             cells: [BingoBoardCell::new(); NUM_CELLS_IN_BINGO_BOARD],
             bingo: false,
@@ -127,14 +158,12 @@ impl BingoGame {
     ) -> Self {
         call_order.reverse();
 
-        let me = BingoGame {
+        BingoGame {
             call_order,
             last_call: None,
             boards,
             strategy,
-        };
-
-        me
+        }
     }
 
     fn has_game_ended(&self) -> bool {
@@ -187,29 +216,10 @@ impl fmt::Display for UnwinableBingoGame {
 }
 impl Error for UnwinableBingoGame {}
 
-use std::fs;
-fn read_input(input_file: &String) -> (Vec<i32>, Vec<BingoBoard>) {
-    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
-
-    let mut call_order_str: Vec<&str> = contents.split("\n\n").collect();
-    let boards_str: Vec<&str> = call_order_str.split_off(1);
-    let call_order_str = call_order_str[0];
-
-    let call_order: Vec<i32> = call_order_str
-        .split(",")
-        .map(|i_str| i_str.parse().unwrap())
-        .collect();
-
-    let mut boards = Vec::new();
-    for b_str in boards_str {
-        let board_nums: Vec<i32> = b_str
-            .split_ascii_whitespace()
-            .map(|i_str| i_str.parse().unwrap())
-            .collect();
-        boards.push(BingoBoard::new(&board_nums));
-    }
-
-    (call_order, boards)
+fn parse_number(i_str: &str) -> Result<i32, ParseError> {
+    i_str
+        .parse()
+        .map_err(|_| ParseError::from(format!("Bad bingo number: {}", i_str)))
 }
 
 // Part 1
@@ -229,18 +239,11 @@ impl EndGameStrategy for FirstWinEndsGame {
     }
 
     fn has_game_ended(&self) -> bool {
-        match self.winner {
-            Some(_) => true,
-            _ => false,
-        }
+        self.winner.is_some()
     }
 
     fn get_end_trigger_board(&self) -> Option<BingoBoard> {
-        if let Some(winner) = &self.winner {
-            Some(winner.clone())
-        } else {
-            None
-        }
+        self.winner.clone()
     }
 }
 
@@ -251,7 +254,7 @@ struct LastWinEndsGame {
     last_winner: Option<BingoBoard>,
 }
 impl LastWinEndsGame {
-    fn new(boards: &Vec<BingoBoard>) -> Self {
+    fn new(boards: &[BingoBoard]) -> Self {
         LastWinEndsGame {
             num_boards: boards.len(),
             num_boards_bingoed: 0,
@@ -272,11 +275,7 @@ impl EndGameStrategy for LastWinEndsGame {
     }
 
     fn get_end_trigger_board(&self) -> Option<BingoBoard> {
-        if let Some(last_winner) = &self.last_winner {
-            Some(last_winner.clone())
-        } else {
-            None
-        }
+        self.last_winner.clone()
     }
 }
 
@@ -1,5 +1,203 @@
-use super::{Day05, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day05;
+pub const META: DayMeta = DayMeta {
+    day: 5,
+    title: "Hydrothermal Venture",
+};
 
 impl DaySolution for Day05 {
-    // TODO
+    type Input = Vec<LineSegment>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_segments(input)?)
+    }
+
+    fn part_1(&self, segments: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let straight_segments: Vec<LineSegment> = segments
+            .iter()
+            .copied()
+            .filter(|s| s.is_horizontal() || s.is_vertical())
+            .collect();
+        Ok(count_overlapping_points(&straight_segments).to_string())
+    }
+
+    fn part_2(&self, segments: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(count_overlapping_points(segments).to_string())
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+use std::str::FromStr;
+impl FromStr for Point {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x_str, y_str) = s
+            .trim()
+            .split_once(',')
+            .ok_or_else(|| format!("Bad point: {}", s))?;
+
+        let x = x_str
+            .parse::<i32>()
+            .map_err(|_| format!("Bad x value for point: {}", s))?;
+        let y = y_str
+            .parse::<i32>()
+            .map_err(|_| format!("Bad y value for point: {}", s))?;
+
+        Ok(Point::new(x, y))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineSegment {
+    start: Point,
+    end: Point,
+}
+impl LineSegment {
+    fn new(start: Point, end: Point) -> Self {
+        LineSegment { start, end }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    fn is_diagonal(&self) -> bool {
+        (self.end.x - self.start.x).abs() == (self.end.y - self.start.y).abs()
+    }
+
+    // Every grid point covered by the segment, from start to end (inclusive).
+    // Only horizontal, vertical and 45° diagonal segments are supported
+    fn points(&self) -> Vec<Point> {
+        if !(self.is_horizontal() || self.is_vertical() || self.is_diagonal()) {
+            return Vec::new();
+        }
+
+        let step_x = (self.end.x - self.start.x).signum();
+        let step_y = (self.end.y - self.start.y).signum();
+        let length = (self.end.x - self.start.x)
+            .abs()
+            .max((self.end.y - self.start.y).abs());
+
+        (0..=length)
+            .map(|i| Point::new(self.start.x + i * step_x, self.start.y + i * step_y))
+            .collect()
+    }
+}
+impl FromStr for LineSegment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start_str, end_str) = s
+            .split_once("->")
+            .ok_or_else(|| format!("Bad line segment: {}", s))?;
+
+        Ok(LineSegment::new(
+            Point::from_str(start_str)?,
+            Point::from_str(end_str)?,
+        ))
+    }
+}
+
+use std::collections::HashMap;
+fn count_overlapping_points(segments: &[LineSegment]) -> usize {
+    let mut vents_per_point: HashMap<Point, i32> = HashMap::new();
+
+    for segment in segments {
+        for point in segment.points() {
+            *vents_per_point.entry(point).or_insert(0) += 1;
+        }
+    }
+
+    vents_per_point.values().filter(|&&n| n >= 2).count()
+}
+
+fn parse_segments(contents: &str) -> Result<Vec<LineSegment>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(LineSegment::from_str)
+        .collect()
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn parse_line_segment() {
+        let segment = LineSegment::from_str("0,9 -> 5,9").unwrap();
+
+        assert_eq!(Point::new(0, 9), segment.start);
+        assert_eq!(Point::new(5, 9), segment.end);
+        assert!(segment.is_horizontal());
+        assert!(!segment.is_vertical());
+    }
+
+    #[test]
+    fn rejects_bad_line_segment() {
+        assert!(LineSegment::from_str("0,9 5,9").is_err());
+        assert!(LineSegment::from_str("0,a -> 5,9").is_err());
+    }
+
+    #[test]
+    fn lists_points_of_segments() {
+        let vertical = LineSegment::from_str("1,1 -> 1,3").unwrap();
+        let diagonal = LineSegment::from_str("9,7 -> 7,9").unwrap();
+
+        assert_eq!(
+            vec![Point::new(1, 1), Point::new(1, 2), Point::new(1, 3)],
+            vertical.points()
+        );
+        assert_eq!(
+            vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)],
+            diagonal.points()
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let segments: Vec<LineSegment> = parse_segments(EXAMPLE)
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.is_horizontal() || s.is_vertical())
+            .collect();
+
+        assert_eq!(5, count_overlapping_points(&segments));
+    }
+
+    #[test]
+    fn part_2() {
+        let segments = parse_segments(EXAMPLE).unwrap();
+
+        assert_eq!(12, count_overlapping_points(&segments));
+    }
 }
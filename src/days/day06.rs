@@ -1,5 +1,128 @@
-use super::{Day06, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day06;
+pub const META: DayMeta = DayMeta {
+    day: 6,
+    title: "Lanternfish",
+};
 
 impl DaySolution for Day06 {
-    // TODO
+    type Input = LanternfishSchool;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_school(input)?)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut school = input.clone();
+        school.simulate(80);
+        Ok(school.population().to_string())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut school = input.clone();
+        school.simulate(256);
+        Ok(school.population().to_string())
+    }
+}
+
+// Shared
+const NEWBORN_TIMER: usize = 8;
+const RESET_TIMER: usize = 6;
+
+// Instead of keeping track of every single fish, we only keep track of how many fish have each timer value
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanternfishSchool {
+    fish_per_timer: [u64; NEWBORN_TIMER + 1],
+}
+impl LanternfishSchool {
+    fn new(timers: &[usize]) -> Self {
+        let mut fish_per_timer = [0; NEWBORN_TIMER + 1];
+        for &timer in timers {
+            fish_per_timer[timer] += 1;
+        }
+
+        LanternfishSchool { fish_per_timer }
+    }
+
+    fn step(&mut self) {
+        let spawning = self.fish_per_timer[0];
+        self.fish_per_timer.rotate_left(1);
+        // rotating already moved the spawning fish into the newborn bucket, they also restart their own timer
+        self.fish_per_timer[RESET_TIMER] += spawning;
+    }
+
+    fn simulate(&mut self, days: usize) {
+        for _ in 0..days {
+            self.step();
+        }
+    }
+
+    fn population(&self) -> u64 {
+        self.fish_per_timer.iter().sum()
+    }
+}
+
+fn parse_school(contents: &str) -> Result<LanternfishSchool, String> {
+    let timers: Vec<usize> = contents
+        .trim()
+        .split(',')
+        .map(|t_str| match t_str.parse() {
+            Ok(timer) if timer <= NEWBORN_TIMER => Ok(timer),
+            _ => Err(format!("Invalid timer: {}", t_str)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(LanternfishSchool::new(&timers))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_fish_per_timer() {
+        let school = parse_school("3,4,3,1,2").unwrap();
+
+        assert_eq!([0, 1, 1, 2, 1, 0, 0, 0, 0], school.fish_per_timer);
+        assert!(parse_school("3,4,9").is_err());
+        assert!(parse_school("3,-1").is_err());
+        assert_eq!(5, school.population());
+    }
+
+    #[test]
+    fn spawns_new_fish() {
+        let mut school = parse_school("3,4,3,1,2").unwrap();
+
+        school.simulate(2);
+        assert_eq!(parse_school("1,2,1,6,0,8").unwrap(), school);
+        school.step();
+        assert_eq!(parse_school("0,1,0,5,6,7,8").unwrap(), school);
+    }
+
+    #[test]
+    fn example_after_18_days() {
+        let mut school = parse_school("3,4,3,1,2").unwrap();
+        school.simulate(18);
+
+        assert_eq!(26, school.population());
+    }
+
+    #[test]
+    fn part_1() {
+        let mut school = parse_school("3,4,3,1,2").unwrap();
+        school.simulate(80);
+
+        assert_eq!(5934, school.population());
+    }
+
+    #[test]
+    fn part_2() {
+        let mut school = parse_school("3,4,3,1,2").unwrap();
+        school.simulate(256);
+
+        assert_eq!(26984457539, school.population());
+    }
 }
@@ -1,5 +1,167 @@
-use super::{Day07, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day07;
+pub const META: DayMeta = DayMeta {
+    day: 7,
+    title: "The Treachery of Whales",
+};
 
 impl DaySolution for Day07 {
-    // TODO
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_crabs(input)?)
+    }
+
+    fn part_1(&self, crabs: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (_, fuel) = find_cheapest_alignment(crabs, &LinearCost);
+        Ok(fuel.to_string())
+    }
+
+    fn part_2(&self, crabs: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (_, fuel) = find_cheapest_alignment(crabs, &TriangularCost);
+        Ok(fuel.to_string())
+    }
+}
+
+// Shared
+// A cost model knows how much fuel a crab burns to move a given distance, and where the optimal
+// alignment position can be, so that we don't need to try every single position
+trait FuelCostModel {
+    fn cost(&self, distance: i64) -> i64;
+    fn candidate_positions(&self, sorted_crabs: &[i64]) -> Vec<i64>;
+}
+
+fn total_fuel(crabs: &[i64], position: i64, model: &dyn FuelCostModel) -> i64 {
+    crabs
+        .iter()
+        .map(|&crab| model.cost((crab - position).abs()))
+        .sum()
+}
+
+// Returns the best position to align all crabs to, and how much fuel it costs
+fn find_cheapest_alignment(crabs: &[i64], model: &dyn FuelCostModel) -> (i64, i64) {
+    let mut sorted_crabs = crabs.to_vec();
+    sorted_crabs.sort_unstable();
+
+    model
+        .candidate_positions(&sorted_crabs)
+        .into_iter()
+        .map(|position| (position, total_fuel(&sorted_crabs, position, model)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap_or((0, 0))
+}
+
+fn parse_crabs(contents: &str) -> Result<Vec<i64>, String> {
+    contents
+        .trim()
+        .split(',')
+        .map(|i_str| {
+            i_str
+                .parse()
+                .map_err(|_| format!("Invalid position: {}", i_str))
+        })
+        .collect()
+}
+
+// Part 1
+// Moving costs 1 fuel per step, so the sum of distances is minimised at the median
+struct LinearCost;
+impl FuelCostModel for LinearCost {
+    fn cost(&self, distance: i64) -> i64 {
+        distance
+    }
+
+    fn candidate_positions(&self, sorted_crabs: &[i64]) -> Vec<i64> {
+        if sorted_crabs.is_empty() {
+            return Vec::new();
+        }
+
+        vec![sorted_crabs[sorted_crabs.len() / 2]]
+    }
+}
+
+// Part 2
+// Each step costs 1 more than the previous one (1 + 2 + ... + n), and the optimal position for
+// this cost is always within 1/2 of the mean, so only the integers around it need checking
+struct TriangularCost;
+impl FuelCostModel for TriangularCost {
+    fn cost(&self, distance: i64) -> i64 {
+        distance * (distance + 1) / 2
+    }
+
+    fn candidate_positions(&self, sorted_crabs: &[i64]) -> Vec<i64> {
+        if sorted_crabs.is_empty() {
+            return Vec::new();
+        }
+
+        let n = sorted_crabs.len() as i64;
+        let sum: i64 = sorted_crabs.iter().sum();
+        let mean_floor = sum.div_euclid(n);
+
+        (mean_floor - 1..=mean_floor + 1).collect()
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn linear_cost() {
+        let crabs = parse_crabs(EXAMPLE).unwrap();
+
+        assert_eq!(1, LinearCost.cost(1));
+        assert_eq!(11, LinearCost.cost(11));
+        assert_eq!(41, total_fuel(&crabs, 1, &LinearCost));
+        assert_eq!(39, total_fuel(&crabs, 3, &LinearCost));
+        assert_eq!(71, total_fuel(&crabs, 10, &LinearCost));
+    }
+
+    #[test]
+    fn triangular_cost() {
+        let crabs = parse_crabs(EXAMPLE).unwrap();
+
+        assert_eq!(1, TriangularCost.cost(1));
+        assert_eq!(66, TriangularCost.cost(11));
+        assert_eq!(206, total_fuel(&crabs, 2, &TriangularCost));
+    }
+
+    #[test]
+    fn part_1() {
+        let crabs = parse_crabs(EXAMPLE).unwrap();
+
+        assert_eq!((2, 37), find_cheapest_alignment(&crabs, &LinearCost));
+    }
+
+    #[test]
+    fn part_2() {
+        let crabs = parse_crabs(EXAMPLE).unwrap();
+
+        assert_eq!((5, 168), find_cheapest_alignment(&crabs, &TriangularCost));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let crabs = vec![0, 0, 0, 0, 100, 3, 7, 7, 51, 2];
+        let brute_force = |model: &dyn FuelCostModel| {
+            (0..=100)
+                .map(|p| total_fuel(&crabs, p, model))
+                .min()
+                .unwrap()
+        };
+
+        assert_eq!(
+            brute_force(&LinearCost),
+            find_cheapest_alignment(&crabs, &LinearCost).1
+        );
+        assert_eq!(
+            brute_force(&TriangularCost),
+            find_cheapest_alignment(&crabs, &TriangularCost).1
+        );
+    }
 }
@@ -1,5 +1,235 @@
-use super::{Day08, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day08;
+pub const META: DayMeta = DayMeta {
+    day: 8,
+    title: "Seven Segment Search",
+};
 
 impl DaySolution for Day08 {
-    // TODO
+    type Input = Vec<SegmentDisplay>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_displays(input)?)
+    }
+
+    fn part_1(&self, displays: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(count_unique_length_outputs(displays).to_string())
+    }
+
+    fn part_2(&self, displays: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut sum = 0;
+        for display in displays {
+            sum += display.decode()?;
+        }
+        Ok(sum.to_string())
+    }
+}
+
+// Shared
+// A pattern of lit wires, where wire 'a' is bit 0, 'b' is bit 1, ..., and 'g' is bit 6
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Pattern(u8);
+impl Pattern {
+    fn num_lit(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    fn contains(&self, other: Pattern) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+use std::str::FromStr;
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut wires = 0;
+        for c in s.chars() {
+            match c {
+                'a'..='g' => wires |= 1 << (c as u8 - b'a'),
+                _ => return Err(format!("Unknown wire '{}' in pattern {}", c, s)),
+            }
+        }
+        Ok(Pattern(wires))
+    }
+}
+
+#[derive(Debug)]
+pub struct SegmentDisplay {
+    signal_patterns: Vec<Pattern>,
+    output: Vec<Pattern>,
+}
+impl SegmentDisplay {
+    // Works out which pattern lights up each digit, using only the ten unique signal patterns:
+    // - 1, 4, 7 and 8 are the only digits with 2, 4, 3 and 7 segments
+    // - out of the 6 segment digits (0, 6, 9), only 9 contains 4, and only 0 contains 1 without containing 4
+    // - out of the 5 segment digits (2, 3, 5), only 3 contains 1, and only 5 is contained by 6
+    fn deduce_digits(&self) -> Result<[Pattern; 10], UndecodableDisplay> {
+        let with_len = |len: u32| -> Vec<Pattern> {
+            self.signal_patterns
+                .iter()
+                .copied()
+                .filter(|p| p.num_lit() == len)
+                .collect()
+        };
+        let only_one = |patterns: &[Pattern],
+                        rule: &dyn Fn(Pattern) -> bool|
+         -> Result<Pattern, UndecodableDisplay> {
+            let matching: Vec<Pattern> = patterns.iter().copied().filter(|&p| rule(p)).collect();
+            match matching[..] {
+                [p] => Ok(p),
+                _ => Err(UndecodableDisplay),
+            }
+        };
+
+        let one = only_one(&with_len(2), &|_| true)?;
+        let four = only_one(&with_len(4), &|_| true)?;
+        let seven = only_one(&with_len(3), &|_| true)?;
+        let eight = only_one(&with_len(7), &|_| true)?;
+
+        let six_segments = with_len(6);
+        let nine = only_one(&six_segments, &|p| p.contains(four))?;
+        let zero = only_one(&six_segments, &|p| !p.contains(four) && p.contains(one))?;
+        let six = only_one(&six_segments, &|p| !p.contains(one))?;
+
+        let five_segments = with_len(5);
+        let three = only_one(&five_segments, &|p| p.contains(one))?;
+        let five = only_one(&five_segments, &|p| six.contains(p))?;
+        let two = only_one(&five_segments, &|p| p != three && p != five)?;
+
+        Ok([zero, one, two, three, four, five, six, seven, eight, nine])
+    }
+
+    fn decode(&self) -> Result<u32, UndecodableDisplay> {
+        let digits = self.deduce_digits()?;
+
+        let mut value = 0;
+        for pattern in &self.output {
+            let digit = digits
+                .iter()
+                .position(|d| d == pattern)
+                .ok_or(UndecodableDisplay)?;
+            value = value * 10 + digit as u32;
+        }
+        Ok(value)
+    }
+}
+impl FromStr for SegmentDisplay {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (patterns_str, output_str) = s
+            .split_once('|')
+            .ok_or_else(|| format!("Missing '|' in display: {}", s))?;
+
+        let signal_patterns = patterns_str
+            .split_ascii_whitespace()
+            .map(Pattern::from_str)
+            .collect::<Result<Vec<Pattern>, String>>()?;
+        let output = output_str
+            .split_ascii_whitespace()
+            .map(Pattern::from_str)
+            .collect::<Result<Vec<Pattern>, String>>()?;
+
+        Ok(SegmentDisplay {
+            signal_patterns,
+            output,
+        })
+    }
+}
+
+use std::fmt;
+#[derive(Debug)]
+pub struct UndecodableDisplay;
+impl fmt::Display for UndecodableDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not deduce the wiring of a display from its signal patterns"
+        )
+    }
+}
+impl Error for UndecodableDisplay {}
+
+fn parse_displays(contents: &str) -> Result<Vec<SegmentDisplay>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(SegmentDisplay::from_str)
+        .collect()
+}
+
+// Part 1
+fn count_unique_length_outputs(displays: &[SegmentDisplay]) -> usize {
+    displays
+        .iter()
+        .flat_map(|d| d.output.iter())
+        .filter(|p| matches!(p.num_lit(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINGLE_EXAMPLE: &str =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    const EXAMPLE: &str =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn parse_display() {
+        let display = SegmentDisplay::from_str(SINGLE_EXAMPLE).unwrap();
+
+        assert_eq!(10, display.signal_patterns.len());
+        assert_eq!(4, display.output.len());
+        assert_eq!(Pattern(0b0000011), Pattern::from_str("ab").unwrap());
+        assert!(Pattern::from_str("abz").is_err());
+    }
+
+    #[test]
+    fn deduces_digits_of_single_example() {
+        let display = SegmentDisplay::from_str(SINGLE_EXAMPLE).unwrap();
+        let digits = display.deduce_digits().unwrap();
+
+        let expected = [
+            "cagedb", "ab", "gcdfa", "fbcad", "eafb", "cdfbe", "cdfgeb", "dab", "acedgfb", "cefabd",
+        ];
+        for (digit, pattern_str) in expected.iter().enumerate() {
+            assert_eq!(Pattern::from_str(pattern_str).unwrap(), digits[digit]);
+        }
+        assert_eq!(5353, display.decode().unwrap());
+    }
+
+    #[test]
+    fn part_1() {
+        let displays = parse_displays(EXAMPLE).unwrap();
+
+        assert_eq!(26, count_unique_length_outputs(&displays));
+    }
+
+    #[test]
+    fn part_2() {
+        let displays = parse_displays(EXAMPLE).unwrap();
+        let decoded: Vec<u32> = displays.iter().map(|d| d.decode().unwrap()).collect();
+
+        assert_eq!(
+            vec![8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315],
+            decoded
+        );
+        assert_eq!(61229, decoded.iter().sum::<u32>());
+    }
 }
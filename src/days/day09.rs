@@ -1,5 +1,122 @@
-use super::{Day09, DaySolution};
+use super::grid::Grid;
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day09;
+pub const META: DayMeta = DayMeta {
+    day: 9,
+    title: "Smoke Basin",
+};
 
 impl DaySolution for Day09 {
-    // TODO
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Grid::from_digits(input)?)
+    }
+
+    fn part_1(&self, height_map: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(sum_risk_levels(height_map).to_string())
+    }
+
+    fn part_2(&self, height_map: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut basin_sizes = find_basin_sizes(height_map);
+        basin_sizes.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(basin_sizes.iter().take(3).product::<usize>().to_string())
+    }
+}
+
+// Shared
+const MAX_HEIGHT: u8 = 9;
+
+fn find_low_points(height_map: &Grid<u8>) -> Vec<(usize, usize)> {
+    height_map
+        .positions()
+        .filter(|&(x, y)| {
+            let height = height_map.get(x, y).unwrap();
+            height_map
+                .neighbours_4(x, y)
+                .all(|(nx, ny)| height < height_map.get(nx, ny).unwrap())
+        })
+        .collect()
+}
+
+// Part 1
+fn sum_risk_levels(height_map: &Grid<u8>) -> u32 {
+    find_low_points(height_map)
+        .iter()
+        .map(|&(x, y)| *height_map.get(x, y).unwrap() as u32 + 1)
+        .sum()
+}
+
+// Part 2
+// Every low point is the bottom of one basin, so we flood fill outwards from it until we hit
+// locations of height 9, which are not part of any basin.
+// Basins never overlap, so the visited locations are shared between all the flood fills
+fn basin_size(height_map: &Grid<u8>, visited: &mut Grid<bool>, low_point: (usize, usize)) -> usize {
+    let mut to_visit = vec![low_point];
+    let mut size = 0;
+
+    while let Some((x, y)) = to_visit.pop() {
+        let seen = visited.get_mut(x, y).unwrap();
+        if *seen || *height_map.get(x, y).unwrap() == MAX_HEIGHT {
+            continue;
+        }
+        *seen = true;
+        size += 1;
+
+        to_visit.extend(height_map.neighbours_4(x, y));
+    }
+
+    size
+}
+
+fn find_basin_sizes(height_map: &Grid<u8>) -> Vec<usize> {
+    let mut visited = Grid::new(
+        height_map.width(),
+        height_map.height(),
+        vec![false; height_map.width() * height_map.height()],
+    );
+
+    find_low_points(height_map)
+        .into_iter()
+        .map(|low_point| basin_size(height_map, &mut visited, low_point))
+        .collect()
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn finds_low_points() {
+        let height_map = Grid::from_digits(EXAMPLE).unwrap();
+
+        assert_eq!(
+            vec![(1, 0), (9, 0), (2, 2), (6, 4)],
+            find_low_points(&height_map)
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let height_map = Grid::from_digits(EXAMPLE).unwrap();
+
+        assert_eq!(15, sum_risk_levels(&height_map));
+    }
+
+    #[test]
+    fn part_2() {
+        let height_map = Grid::from_digits(EXAMPLE).unwrap();
+
+        assert_eq!(vec![3, 9, 14, 9], find_basin_sizes(&height_map));
+        assert_eq!("1134", Day09.part_2(&height_map).unwrap());
+    }
 }
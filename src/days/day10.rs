@@ -1,5 +1,279 @@
-use super::{Day10, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day10;
+pub const META: DayMeta = DayMeta {
+    day: 10,
+    title: "Syntax Scoring",
+};
 
 impl DaySolution for Day10 {
-    // TODO
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part_1(&self, lines: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(total_syntax_error_score(lines).to_string())
+    }
+
+    fn part_2(&self, lines: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match middle_completion_score(lines) {
+            Some(score) => Ok(score.to_string()),
+            None => Err(Box::new(NoIncompleteLines)),
+        }
+    }
+}
+
+// Shared
+#[derive(Debug, PartialEq)]
+enum LineStatus {
+    Valid,
+    // The first closing character that does not match the currently open chunk
+    // (nothing is expected if there was no open chunk left to close)
+    Corrupted {
+        expected: Option<char>,
+        found: char,
+        column: usize,
+    },
+    // All the closing characters needed to finish the open chunks, in order
+    Incomplete {
+        completion: String,
+    },
+}
+
+fn closing_pair(opening: char) -> Option<char> {
+    match opening {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+// Lines can only be made of chunk delimiters
+fn parse_lines(contents: &str) -> Result<Vec<String>, String> {
+    contents
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| match l.chars().find(|c| !"()[]{}<>".contains(*c)) {
+            Some(c) => Err(format!("Unexpected character '{}' in line: {}", c, l)),
+            None => Ok(String::from(l)),
+        })
+        .collect()
+}
+
+fn check_line(line: &str) -> LineStatus {
+    let mut expected_closings: Vec<char> = Vec::new();
+
+    for (column, c) in line.chars().enumerate() {
+        if let Some(closing) = closing_pair(c) {
+            expected_closings.push(closing);
+            continue;
+        }
+
+        match expected_closings.pop() {
+            Some(expected) if expected == c => {}
+            expected => {
+                return LineStatus::Corrupted {
+                    expected,
+                    found: c,
+                    column,
+                }
+            }
+        }
+    }
+
+    if expected_closings.is_empty() {
+        LineStatus::Valid
+    } else {
+        LineStatus::Incomplete {
+            completion: expected_closings.iter().rev().collect(),
+        }
+    }
+}
+
+use std::fmt;
+#[derive(Debug)]
+pub struct NoIncompleteLines;
+impl fmt::Display for NoIncompleteLines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No incomplete lines found in the navigation subsystem")
+    }
+}
+impl Error for NoIncompleteLines {}
+
+// Part 1
+fn syntax_error_score(illegal: char) -> u64 {
+    match illegal {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => 0,
+    }
+}
+
+fn total_syntax_error_score(lines: &[String]) -> u64 {
+    lines
+        .iter()
+        .map(|l| match check_line(l) {
+            LineStatus::Corrupted { found, .. } => syntax_error_score(found),
+            _ => 0,
+        })
+        .sum()
+}
+
+// Part 2
+fn completion_score(completion: &str) -> u64 {
+    completion.chars().fold(0, |score, c| {
+        let value = match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            _ => 0,
+        };
+        score * 5 + value
+    })
+}
+
+fn middle_completion_score(lines: &[String]) -> Option<u64> {
+    let mut scores: Vec<u64> = lines
+        .iter()
+        .filter_map(|l| match check_line(l) {
+            LineStatus::Incomplete { completion } => Some(completion_score(&completion)),
+            _ => None,
+        })
+        .collect();
+
+    if scores.is_empty() {
+        return None;
+    }
+
+    scores.sort_unstable();
+    Some(scores[scores.len() / 2])
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 10] = [
+        "[({(<(())[]>[[{[]{<()<>>",
+        "[(()[<>])]({[<{<<[]>>(",
+        "{([(<{}[<>[]}>{[]{[(<()>",
+        "(((({<>}<{<{<>}{[]{[]{}",
+        "[[<[([]))<([[{}[[()]]]",
+        "[{[{({}]{}}([{[{{{}}([]",
+        "{<[[]]>}<{[{[{[]{()[[[]",
+        "[<(<(<(<{}))><([]([]()",
+        "<{([([[(<>()){}]>(<<{{",
+        "<{([{{}}[<[[[<>{}]]]>[]]",
+    ];
+
+    fn example_lines() -> Vec<String> {
+        EXAMPLE.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(Ok(example_lines()), parse_lines(&EXAMPLE.join("\n")));
+        assert_eq!(
+            Ok(vec![String::from("([])"), String::from("<>")]),
+            parse_lines("([])\r\n\n<>\n")
+        );
+        assert!(parse_lines("hello\nworld").is_err());
+        assert!(parse_lines("(a)").is_err());
+    }
+
+    #[test]
+    fn checks_valid_lines() {
+        assert_eq!(LineStatus::Valid, check_line("([])"));
+        assert_eq!(LineStatus::Valid, check_line("[<>({}){}[([])<>]]"));
+    }
+
+    #[test]
+    fn finds_corrupted_characters() {
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: Some(']'),
+                found: '}',
+                column: 12
+            },
+            check_line(EXAMPLE[2])
+        );
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: Some(']'),
+                found: ')',
+                column: 8
+            },
+            check_line(EXAMPLE[4])
+        );
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: Some(')'),
+                found: ']',
+                column: 7
+            },
+            check_line(EXAMPLE[5])
+        );
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: Some('>'),
+                found: ')',
+                column: 10
+            },
+            check_line(EXAMPLE[7])
+        );
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: Some(']'),
+                found: '>',
+                column: 16
+            },
+            check_line(EXAMPLE[8])
+        );
+        assert_eq!(
+            LineStatus::Corrupted {
+                expected: None,
+                found: ')',
+                column: 2
+            },
+            check_line("())")
+        );
+    }
+
+    #[test]
+    fn finds_completions() {
+        assert_eq!(
+            LineStatus::Incomplete {
+                completion: String::from("}}]])})]")
+            },
+            check_line(EXAMPLE[0])
+        );
+        assert_eq!(
+            LineStatus::Incomplete {
+                completion: String::from("])}>")
+            },
+            check_line(EXAMPLE[9])
+        );
+        assert_eq!(288957, completion_score("}}]])})]"));
+        assert_eq!(294, completion_score("])}>"));
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(26397, total_syntax_error_score(&example_lines()));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Some(288957), middle_completion_score(&example_lines()));
+    }
 }
@@ -1,5 +1,205 @@
-use super::{Day11, DaySolution};
+use super::grid::Grid;
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day11;
+pub const META: DayMeta = DayMeta {
+    day: 11,
+    title: "Dumbo Octopus",
+};
 
 impl DaySolution for Day11 {
-    // TODO
+    type Input = OctopusGrid;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(OctopusGrid::new(Grid::from_digits(input)?))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut octopuses = input.clone();
+        Ok(octopuses.count_flashes(100).to_string())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut octopuses = input.clone();
+        Ok(octopuses.find_first_synchronized_step().to_string())
+    }
+}
+
+// Shared
+const FLASH_THRESHOLD: u8 = 9;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OctopusGrid {
+    energy: Grid<u8>,
+}
+impl OctopusGrid {
+    fn new(energy: Grid<u8>) -> Self {
+        OctopusGrid { energy }
+    }
+
+    // Advances the simulation by one step, and returns how many octopuses flashed during it
+    fn step(&mut self) -> usize {
+        let positions: Vec<(usize, usize)> = self.energy.positions().collect();
+        let mut about_to_flash: Vec<(usize, usize)> = Vec::new();
+
+        for &(x, y) in &positions {
+            if self.increase_energy(x, y) {
+                about_to_flash.push((x, y));
+            }
+        }
+
+        // each octopus can only flash once per step, which is guaranteed by only queueing it
+        // when its energy first goes over the threshold
+        let mut num_flashes = 0;
+        while let Some((x, y)) = about_to_flash.pop() {
+            num_flashes += 1;
+
+            let neighbours: Vec<(usize, usize)> = self.energy.neighbours_8(x, y).collect();
+            for (nx, ny) in neighbours {
+                if self.increase_energy(nx, ny) {
+                    about_to_flash.push((nx, ny));
+                }
+            }
+        }
+
+        for (x, y) in positions {
+            let energy = self.energy.get_mut(x, y).unwrap();
+            if *energy > FLASH_THRESHOLD {
+                *energy = 0;
+            }
+        }
+
+        num_flashes
+    }
+
+    // Returns whether this increase made the octopus go over the threshold
+    fn increase_energy(&mut self, x: usize, y: usize) -> bool {
+        let energy = self.energy.get_mut(x, y).unwrap();
+        *energy += 1;
+        *energy == FLASH_THRESHOLD + 1
+    }
+
+    fn num_octopuses(&self) -> usize {
+        self.energy.width() * self.energy.height()
+    }
+}
+
+// Part 1
+impl OctopusGrid {
+    fn count_flashes(&mut self, steps: usize) -> usize {
+        (0..steps).map(|_| self.step()).sum()
+    }
+}
+
+// Part 2
+impl OctopusGrid {
+    fn find_first_synchronized_step(&mut self) -> usize {
+        let mut step = 1;
+        while self.step() != self.num_octopuses() {
+            step += 1;
+        }
+        step
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    fn octopuses(s: &str) -> OctopusGrid {
+        OctopusGrid::new(Grid::from_digits(s).unwrap())
+    }
+
+    #[test]
+    fn cascades_flashes_in_small_example() {
+        let mut grid = octopuses("11111\n19991\n19191\n19991\n11111");
+
+        assert_eq!(9, grid.step());
+        assert_eq!(octopuses("34543\n40004\n50005\n40004\n34543"), grid);
+        assert_eq!(0, grid.step());
+        assert_eq!(octopuses("45654\n51115\n61116\n51115\n45654"), grid);
+    }
+
+    #[test]
+    fn steps_through_example() {
+        let mut grid = octopuses(EXAMPLE);
+
+        assert_eq!(0, grid.step());
+        assert_eq!(
+            octopuses(
+                "6594254334
+3856965822
+6375667284
+7252447257
+7468496589
+5278635756
+3287952832
+7993992245
+5957959665
+6394862637"
+            ),
+            grid
+        );
+
+        assert_eq!(35, grid.step());
+        assert_eq!(
+            octopuses(
+                "8807476555
+5089087054
+8597889608
+8485769600
+8700908800
+6600088989
+6800005943
+0000007456
+9000000876
+8700006848"
+            ),
+            grid
+        );
+
+        assert_eq!(204 - 35, grid.count_flashes(8));
+        assert_eq!(
+            octopuses(
+                "0481112976
+0031112009
+0041112504
+0081111406
+0099111306
+0093511233
+0442361130
+5532252350
+0532250600
+0032240000"
+            ),
+            grid
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let mut grid = octopuses(EXAMPLE);
+
+        assert_eq!(1656, grid.count_flashes(100));
+    }
+
+    #[test]
+    fn part_2() {
+        let mut grid = octopuses(EXAMPLE);
+
+        assert_eq!(195, grid.find_first_synchronized_step());
+    }
 }
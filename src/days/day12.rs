@@ -1,5 +1,284 @@
-use super::{Day12, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day12;
+pub const META: DayMeta = DayMeta {
+    day: 12,
+    title: "Passage Pathing",
+};
 
 impl DaySolution for Day12 {
-    // TODO
+    type Input = CaveGraph;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(CaveGraph::from_str(input)?)
+    }
+
+    fn part_1(&self, graph: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(graph
+            .count_paths(RevisitPolicy::NoSmallCaveTwice)
+            .to_string())
+    }
+
+    fn part_2(&self, graph: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(graph
+            .count_paths(RevisitPolicy::OneSmallCaveTwice)
+            .to_string())
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CaveKind {
+    Big,
+    Small,
+}
+
+type CaveId = usize;
+
+// Cave names are interned, so that each cave is just an index into `kinds` and `connections`
+#[derive(Debug)]
+pub struct CaveGraph {
+    names: Vec<String>,
+    kinds: Vec<CaveKind>,
+    connections: Vec<Vec<CaveId>>,
+    start: CaveId,
+    end: CaveId,
+}
+impl CaveGraph {
+    fn new() -> Self {
+        let mut me = CaveGraph {
+            names: Vec::new(),
+            kinds: Vec::new(),
+            connections: Vec::new(),
+            start: 0,
+            end: 0,
+        };
+        me.start = me.intern("start");
+        me.end = me.intern("end");
+
+        me
+    }
+
+    fn intern(&mut self, name: &str) -> CaveId {
+        if let Some(id) = self.names.iter().position(|n| n == name) {
+            return id;
+        }
+
+        let kind = if name.chars().all(|c| c.is_ascii_uppercase()) {
+            CaveKind::Big
+        } else {
+            CaveKind::Small
+        };
+        self.names.push(String::from(name));
+        self.kinds.push(kind);
+        self.connections.push(Vec::new());
+
+        self.names.len() - 1
+    }
+
+    fn connect(&mut self, a: &str, b: &str) -> Result<(), String> {
+        let a = self.intern(a);
+        let b = self.intern(b);
+
+        // two connected big caves could be walked back and forth forever
+        if self.kinds[a] == CaveKind::Big && self.kinds[b] == CaveKind::Big {
+            return Err(format!(
+                "Big caves {} and {} are connected, there would be infinite paths",
+                self.names[a], self.names[b]
+            ));
+        }
+
+        self.connections[a].push(b);
+        self.connections[b].push(a);
+        Ok(())
+    }
+}
+use std::str::FromStr;
+impl FromStr for CaveGraph {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = CaveGraph::new();
+
+        for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| format!("Bad connection: {}", line))?;
+            graph.connect(a, b)?;
+        }
+
+        if graph.names.len() > 64 {
+            return Err(String::from("Too many caves, at most 64 are supported"));
+        }
+
+        Ok(graph)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RevisitPolicy {
+    NoSmallCaveTwice,
+    OneSmallCaveTwice,
+}
+
+use std::collections::HashMap;
+// The number of ways to reach the end from a cave only depends on the cave, on which small caves
+// were already visited, and on whether the one allowed revisit was already used, so we memoise on that
+type PathMemo = HashMap<(CaveId, u64, bool), u64>;
+
+impl CaveGraph {
+    fn count_paths(&self, policy: RevisitPolicy) -> u64 {
+        let revisit_available = policy == RevisitPolicy::OneSmallCaveTwice;
+        let mut memo = PathMemo::new();
+
+        self.count_paths_from(self.start, 1 << self.start, revisit_available, &mut memo)
+    }
+
+    fn count_paths_from(
+        &self,
+        cave: CaveId,
+        visited_small: u64,
+        revisit_available: bool,
+        memo: &mut PathMemo,
+    ) -> u64 {
+        if cave == self.end {
+            return 1;
+        }
+
+        let key = (cave, visited_small, revisit_available);
+        if let Some(&count) = memo.get(&key) {
+            return count;
+        }
+
+        let mut count = 0;
+        for &next in &self.connections[cave] {
+            if next == self.start {
+                continue;
+            }
+
+            let next_mask = 1 << next;
+            if self.kinds[next] == CaveKind::Big {
+                count += self.count_paths_from(next, visited_small, revisit_available, memo);
+            } else if visited_small & next_mask == 0 {
+                count +=
+                    self.count_paths_from(next, visited_small | next_mask, revisit_available, memo);
+            } else if revisit_available {
+                count += self.count_paths_from(next, visited_small, false, memo);
+            }
+        }
+
+        memo.insert(key, count);
+        count
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    const LARGER_EXAMPLE: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+    const EVEN_LARGER_EXAMPLE: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+    #[test]
+    fn parses_cave_graph() {
+        let graph = CaveGraph::from_str(SMALL_EXAMPLE).unwrap();
+
+        assert_eq!(6, graph.names.len());
+        assert_eq!("start", graph.names[graph.start]);
+        assert_eq!("end", graph.names[graph.end]);
+        assert_eq!(
+            CaveKind::Big,
+            graph.kinds[graph.names.iter().position(|n| n == "A").unwrap()]
+        );
+        assert_eq!(
+            CaveKind::Small,
+            graph.kinds[graph.names.iter().position(|n| n == "b").unwrap()]
+        );
+        assert!(CaveGraph::from_str("start-A\nA-B\nB-end").is_err());
+    }
+
+    #[test]
+    fn part_1() {
+        let policy = RevisitPolicy::NoSmallCaveTwice;
+
+        assert_eq!(
+            10,
+            CaveGraph::from_str(SMALL_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+        assert_eq!(
+            19,
+            CaveGraph::from_str(LARGER_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+        assert_eq!(
+            226,
+            CaveGraph::from_str(EVEN_LARGER_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+    }
+
+    #[test]
+    fn part_2() {
+        let policy = RevisitPolicy::OneSmallCaveTwice;
+
+        assert_eq!(
+            36,
+            CaveGraph::from_str(SMALL_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+        assert_eq!(
+            103,
+            CaveGraph::from_str(LARGER_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+        assert_eq!(
+            3509,
+            CaveGraph::from_str(EVEN_LARGER_EXAMPLE)
+                .unwrap()
+                .count_paths(policy)
+        );
+    }
 }
@@ -1,5 +1,220 @@
-use super::{Day13, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day13;
+pub const META: DayMeta = DayMeta {
+    day: 13,
+    title: "Transparent Origami",
+};
 
 impl DaySolution for Day13 {
-    // TODO
+    type Input = (TransparentPaper, Vec<Fold>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (mut paper, folds) = input.clone();
+        if let Some(&first_fold) = folds.first() {
+            paper.fold(first_fold);
+        }
+        Ok(paper.num_dots().to_string())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (mut paper, folds) = input.clone();
+        for fold in folds {
+            paper.fold(fold);
+        }
+        Ok(paper.render())
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fold {
+    AlongX(i32),
+    AlongY(i32),
+}
+use std::str::FromStr;
+impl FromStr for Fold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (axis, line_str) = s
+            .trim()
+            .strip_prefix("fold along ")
+            .and_then(|f| f.split_once('='))
+            .ok_or_else(|| format!("Bad fold instruction: {}", s))?;
+        let line = line_str
+            .parse::<i32>()
+            .map_err(|_| format!("Bad fold line: {}", s))?;
+
+        match axis {
+            "x" => Ok(Fold::AlongX(line)),
+            "y" => Ok(Fold::AlongY(line)),
+            _ => Err(format!("Unknown fold axis: {}", s)),
+        }
+    }
+}
+
+use std::collections::HashSet;
+// Only the positions of the dots are stored, the paper itself can be any size
+#[derive(Clone, Debug)]
+pub struct TransparentPaper {
+    dots: HashSet<(i32, i32)>,
+}
+impl TransparentPaper {
+    fn fold(&mut self, fold: Fold) {
+        // a dot past the fold line ends up mirrored on the other side of it
+        let mirror = |coord: i32, line: i32| {
+            if coord > line {
+                2 * line - coord
+            } else {
+                coord
+            }
+        };
+
+        self.dots = self
+            .dots
+            .iter()
+            .map(|&(x, y)| match fold {
+                Fold::AlongX(line) => (mirror(x, line), y),
+                Fold::AlongY(line) => (x, mirror(y, line)),
+            })
+            .collect();
+    }
+
+    fn num_dots(&self) -> usize {
+        self.dots.len()
+    }
+
+    fn render(&self) -> String {
+        let max_x = self.dots.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let max_y = self.dots.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+
+        let mut rendered = String::new();
+        for y in 0..=max_y {
+            for x in 0..=max_x {
+                rendered.push(if self.dots.contains(&(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+impl FromStr for TransparentPaper {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut dots = HashSet::new();
+        for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            let (x_str, y_str) = line
+                .split_once(',')
+                .ok_or_else(|| format!("Bad dot: {}", line))?;
+            let x = x_str
+                .parse::<i32>()
+                .map_err(|_| format!("Bad x value for dot: {}", line))?;
+            let y = y_str
+                .parse::<i32>()
+                .map_err(|_| format!("Bad y value for dot: {}", line))?;
+            dots.insert((x, y));
+        }
+
+        Ok(TransparentPaper { dots })
+    }
+}
+
+fn parse_instructions(contents: &str) -> Result<(TransparentPaper, Vec<Fold>), String> {
+    let contents = contents.replace("\r\n", "\n");
+    let (dots_str, folds_str) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| String::from("Missing empty line between dots and folds"))?;
+
+    let paper = TransparentPaper::from_str(dots_str)?;
+    let folds = folds_str
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Fold::from_str)
+        .collect::<Result<Vec<Fold>, String>>()?;
+
+    Ok((paper, folds))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn parses_instructions() {
+        let (paper, folds) = parse_instructions(EXAMPLE).unwrap();
+
+        assert_eq!(18, paper.num_dots());
+        assert_eq!(vec![Fold::AlongY(7), Fold::AlongX(5)], folds);
+        assert!(Fold::from_str("fold along z=3").is_err());
+    }
+
+    #[test]
+    fn part_1() {
+        let (mut paper, folds) = parse_instructions(EXAMPLE).unwrap();
+        paper.fold(folds[0]);
+
+        assert_eq!(17, paper.num_dots());
+        assert_eq!(
+            "#.##..#..#.
+#...#......
+......#...#
+#...#......
+.#.#..#.###
+",
+            paper.render()
+        );
+    }
+
+    #[test]
+    fn part_2() {
+        let (mut paper, folds) = parse_instructions(EXAMPLE).unwrap();
+        for fold in folds {
+            paper.fold(fold);
+        }
+
+        assert_eq!(16, paper.num_dots());
+        assert_eq!(
+            "#####
+#...#
+#...#
+#...#
+#####
+",
+            paper.render()
+        );
+    }
 }
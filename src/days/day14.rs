@@ -1,5 +1,210 @@
-use super::{Day14, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day14;
+pub const META: DayMeta = DayMeta {
+    day: 14,
+    title: "Extended Polymerization",
+};
 
 impl DaySolution for Day14 {
-    // TODO
+    type Input = (PolymerTemplate, InsertionRules);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_manual(input)?)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (template, rules) = input;
+        Ok(template.evolve(rules, 10).score().to_string())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (template, rules) = input;
+        Ok(template.evolve(rules, 40).score().to_string())
+    }
+}
+
+// Shared
+use std::collections::HashMap;
+type Pair = (char, char);
+
+// Maps each pair of adjacent elements to the element inserted between them
+pub struct InsertionRules(HashMap<Pair, char>);
+use std::str::FromStr;
+impl FromStr for InsertionRules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = HashMap::new();
+        for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            let (pair_str, insert_str) = line
+                .split_once(" -> ")
+                .ok_or_else(|| format!("Bad insertion rule: {}", line))?;
+
+            let pair: Vec<char> = pair_str.chars().collect();
+            let insert: Vec<char> = insert_str.chars().collect();
+            match (&pair[..], &insert[..]) {
+                (&[a, b], &[c]) => rules.insert((a, b), c),
+                _ => return Err(format!("Bad insertion rule: {}", line)),
+            };
+        }
+
+        Ok(InsertionRules(rules))
+    }
+}
+
+// A polymer only keeps track of how many times each pair of adjacent elements shows up, instead of
+// the whole (exponentially growing) chain.
+// The last element never changes, and it is the only element that does not start a pair, so it is
+// kept to be able to count elements from the pairs
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolymerTemplate {
+    pair_counts: HashMap<Pair, u64>,
+    last_element: Option<char>,
+}
+impl PolymerTemplate {
+    fn evolve(&self, rules: &InsertionRules, steps: usize) -> PolymerTemplate {
+        let mut polymer = self.clone();
+        for _ in 0..steps {
+            polymer = polymer.step(rules);
+        }
+        polymer
+    }
+
+    fn step(&self, rules: &InsertionRules) -> PolymerTemplate {
+        let mut pair_counts = HashMap::new();
+
+        for (&(a, b), &count) in &self.pair_counts {
+            match rules.0.get(&(a, b)) {
+                Some(&c) => {
+                    *pair_counts.entry((a, c)).or_insert(0) += count;
+                    *pair_counts.entry((c, b)).or_insert(0) += count;
+                }
+                None => *pair_counts.entry((a, b)).or_insert(0) += count,
+            }
+        }
+
+        PolymerTemplate {
+            pair_counts,
+            last_element: self.last_element,
+        }
+    }
+
+    fn element_counts(&self) -> HashMap<char, u64> {
+        let mut counts = HashMap::new();
+        for (&(a, _), &count) in &self.pair_counts {
+            *counts.entry(a).or_insert(0) += count;
+        }
+        if let Some(last) = self.last_element {
+            *counts.entry(last).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    // Quantity of the most common element minus the quantity of the least common element
+    fn score(&self) -> u64 {
+        let counts = self.element_counts();
+        let most_common = counts.values().max().unwrap_or(&0);
+        let least_common = counts.values().min().unwrap_or(&0);
+        most_common - least_common
+    }
+}
+impl FromStr for PolymerTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elements: Vec<char> = s.trim().chars().collect();
+
+        let mut pair_counts = HashMap::new();
+        for pair in elements.windows(2) {
+            *pair_counts.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+
+        Ok(PolymerTemplate {
+            pair_counts,
+            last_element: elements.last().copied(),
+        })
+    }
+}
+
+fn parse_manual(contents: &str) -> Result<(PolymerTemplate, InsertionRules), String> {
+    let contents = contents.replace("\r\n", "\n");
+    let (template_str, rules_str) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| String::from("Missing empty line between template and rules"))?;
+
+    Ok((
+        PolymerTemplate::from_str(template_str)?,
+        InsertionRules::from_str(rules_str)?,
+    ))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    fn polymer_length(polymer: &PolymerTemplate) -> u64 {
+        polymer.element_counts().values().sum()
+    }
+
+    #[test]
+    fn evolves_pairs_like_the_full_chain() {
+        let (template, rules) = parse_manual(EXAMPLE).unwrap();
+
+        assert_eq!(
+            PolymerTemplate::from_str("NCNBCHB").unwrap(),
+            template.evolve(&rules, 1)
+        );
+        assert_eq!(
+            PolymerTemplate::from_str("NBCCNBBBCBHCB").unwrap(),
+            template.evolve(&rules, 2)
+        );
+        assert_eq!(
+            PolymerTemplate::from_str("NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB").unwrap(),
+            template.evolve(&rules, 4)
+        );
+        assert_eq!(97, polymer_length(&template.evolve(&rules, 5)));
+        assert_eq!(3073, polymer_length(&template.evolve(&rules, 10)));
+    }
+
+    #[test]
+    fn part_1() {
+        let (template, rules) = parse_manual(EXAMPLE).unwrap();
+        let polymer = template.evolve(&rules, 10);
+        let counts = polymer.element_counts();
+
+        assert_eq!(1749, counts[&'B']);
+        assert_eq!(298, counts[&'C']);
+        assert_eq!(161, counts[&'H']);
+        assert_eq!(865, counts[&'N']);
+        assert_eq!(1588, polymer.score());
+    }
+
+    #[test]
+    fn part_2() {
+        let (template, rules) = parse_manual(EXAMPLE).unwrap();
+
+        assert_eq!(2188189693529, template.evolve(&rules, 40).score());
+    }
 }
@@ -1,5 +1,208 @@
-use super::{Day15, DaySolution};
+use super::grid::Grid;
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day15;
+pub const META: DayMeta = DayMeta {
+    day: 15,
+    title: "Chiton",
+};
 
 impl DaySolution for Day15 {
-    // TODO
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_risk_map(input)?)
+    }
+
+    fn part_1(&self, risk_map: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match lowest_total_risk(risk_map) {
+            Some(risk) => Ok(risk.to_string()),
+            None => Err(Box::new(NoPathFound)),
+        }
+    }
+
+    fn part_2(&self, tile: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let risk_map = TiledRiskMap::new(tile, 5);
+        match lowest_total_risk(&risk_map) {
+            Some(risk) => Ok(risk.to_string()),
+            None => Err(Box::new(NoPathFound)),
+        }
+    }
+}
+
+// Shared
+// Risk levels wrap around from 9 to 1 when the map is tiled, so 0 is not a valid risk level
+fn parse_risk_map(contents: &str) -> Result<Grid<u8>, String> {
+    let risk_map = Grid::from_digits(contents)?;
+    if risk_map
+        .positions()
+        .any(|(x, y)| *risk_map.get(x, y).unwrap() == 0)
+    {
+        return Err(String::from("Risk levels must be between 1 and 9"));
+    }
+    Ok(risk_map)
+}
+
+trait RiskMap {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn risk(&self, x: usize, y: usize) -> u32;
+}
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+// Dijkstra's algorithm from the top left to the bottom right position.
+// Entering a position costs its risk level, and the starting position is never entered.
+fn lowest_total_risk(risk_map: &dyn RiskMap) -> Option<u32> {
+    let (width, height) = (risk_map.width(), risk_map.height());
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let target = (width - 1, height - 1);
+
+    let mut best_risk = vec![u32::MAX; width * height];
+    let mut to_visit = BinaryHeap::new();
+    best_risk[0] = 0;
+    to_visit.push(Reverse((0, 0, 0)));
+
+    while let Some(Reverse((risk, x, y))) = to_visit.pop() {
+        if (x, y) == target {
+            return Some(risk);
+        }
+        if risk > best_risk[y * width + x] {
+            // a cheaper way to this position was already found
+            continue;
+        }
+
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (nx, ny) in neighbours {
+            if nx >= width || ny >= height {
+                continue;
+            }
+
+            let next_risk = risk + risk_map.risk(nx, ny);
+            if next_risk < best_risk[ny * width + nx] {
+                best_risk[ny * width + nx] = next_risk;
+                to_visit.push(Reverse((next_risk, nx, ny)));
+            }
+        }
+    }
+
+    None
+}
+
+use std::fmt;
+#[derive(Debug)]
+pub struct NoPathFound;
+impl fmt::Display for NoPathFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "There is no path through the cavern")
+    }
+}
+impl Error for NoPathFound {}
+
+// Part 1
+impl RiskMap for Grid<u8> {
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn risk(&self, x: usize, y: usize) -> u32 {
+        *self.get(x, y).unwrap() as u32
+    }
+}
+
+// Part 2
+// The full map is the original tile repeated in both directions, where each repetition to the right
+// or downwards adds 1 to the risk levels, wrapping from 9 back to 1.
+// Risk levels are computed when they are looked up, instead of storing the whole map.
+struct TiledRiskMap<'a> {
+    tile: &'a Grid<u8>,
+    repetitions: usize,
+}
+impl<'a> TiledRiskMap<'a> {
+    fn new(tile: &'a Grid<u8>, repetitions: usize) -> Self {
+        TiledRiskMap { tile, repetitions }
+    }
+}
+impl RiskMap for TiledRiskMap<'_> {
+    fn width(&self) -> usize {
+        self.tile.width() * self.repetitions
+    }
+
+    fn height(&self) -> usize {
+        self.tile.height() * self.repetitions
+    }
+
+    fn risk(&self, x: usize, y: usize) -> u32 {
+        let (tile_width, tile_height) = (self.tile.width(), self.tile.height());
+        let increment = (x / tile_width + y / tile_height) as u32;
+        let original = *self.tile.get(x % tile_width, y % tile_height).unwrap() as u32;
+
+        (original - 1 + increment) % 9 + 1
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn rejects_zero_risk_levels() {
+        assert!(parse_risk_map(EXAMPLE).is_ok());
+        assert!(parse_risk_map("10\n01").is_err());
+    }
+
+    #[test]
+    fn tiles_risk_levels() {
+        let tile = Grid::from_digits("8").unwrap();
+        let risk_map = TiledRiskMap::new(&tile, 5);
+
+        let first_row: Vec<u32> = (0..5).map(|x| risk_map.risk(x, 0)).collect();
+        assert_eq!(vec![8, 9, 1, 2, 3], first_row);
+        assert_eq!(7, risk_map.risk(4, 4));
+
+        let tile = Grid::from_digits(EXAMPLE).unwrap();
+        let risk_map = TiledRiskMap::new(&tile, 5);
+        assert_eq!(50, risk_map.width());
+        assert_eq!(2, risk_map.risk(10, 0));
+        assert_eq!(9, risk_map.risk(49, 49));
+    }
+
+    #[test]
+    fn part_1() {
+        let risk_map = Grid::from_digits(EXAMPLE).unwrap();
+
+        assert_eq!(Some(40), lowest_total_risk(&risk_map));
+    }
+
+    #[test]
+    fn part_2() {
+        let tile = Grid::from_digits(EXAMPLE).unwrap();
+        let risk_map = TiledRiskMap::new(&tile, 5);
+
+        assert_eq!(Some(315), lowest_total_risk(&risk_map));
+    }
 }
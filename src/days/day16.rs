@@ -1,5 +1,394 @@
-use super::{Day16, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day16;
+pub const META: DayMeta = DayMeta {
+    day: 16,
+    title: "Packet Decoder",
+};
 
 impl DaySolution for Day16 {
-    // TODO
+    type Input = Packet;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Packet::from_str(input).map_err(|e| ParseError::from(e.to_string()))
+    }
+
+    fn part_1(&self, packet: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(packet.version_sum().to_string())
+    }
+
+    fn part_2(&self, packet: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(packet.evaluate()?.to_string())
+    }
+}
+
+// Shared
+#[derive(Debug, PartialEq)]
+pub enum PacketError {
+    InvalidHex(char),
+    // The transmission ended in the middle of a packet
+    UnexpectedEnd,
+    UnknownOperator(u8),
+    // The literal value does not fit in 64 bits
+    LiteralTooLarge,
+    // The operator cannot be applied to this many sub-packets
+    BadOperandCount(Operation, usize),
+    // A length type 0 operator's sub-packets did not use exactly the announced number of bits
+    BadSubPacketsLength,
+    // A sum or product of the sub-packets' values does not fit in 64 bits
+    Overflow,
+}
+use std::fmt;
+impl fmt::Display for PacketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PacketError::InvalidHex(c) => write!(f, "Invalid hexadecimal character '{}'", c),
+            PacketError::UnexpectedEnd => write!(f, "Transmission ended in the middle of a packet"),
+            PacketError::UnknownOperator(type_id) => {
+                write!(f, "Unknown operator packet type {}", type_id)
+            }
+            PacketError::LiteralTooLarge => write!(f, "Literal value does not fit in 64 bits"),
+            PacketError::BadOperandCount(operation, count) => write!(
+                f,
+                "Operator {:?} cannot be applied to {} sub-packets",
+                operation, count
+            ),
+            PacketError::BadSubPacketsLength => write!(
+                f,
+                "Sub-packets do not match the total length announced by their operator"
+            ),
+            PacketError::Overflow => write!(f, "Packet value does not fit in 64 bits"),
+        }
+    }
+}
+impl Error for PacketError {}
+
+// Reads a transmission bit by bit, most significant bit first
+struct BitReader {
+    bits: Vec<bool>,
+    pos: usize,
+}
+impl BitReader {
+    fn from_hex(hex: &str) -> Result<Self, PacketError> {
+        let mut bits = Vec::with_capacity(hex.len() * 4);
+        for c in hex.trim().chars() {
+            let nibble = c.to_digit(16).ok_or(PacketError::InvalidHex(c))?;
+            for shift in (0..4).rev() {
+                bits.push((nibble >> shift) & 1 == 1);
+            }
+        }
+
+        Ok(BitReader { bits, pos: 0 })
+    }
+
+    fn read(&mut self, num_bits: usize) -> Result<u64, PacketError> {
+        if self.pos + num_bits > self.bits.len() {
+            return Err(PacketError::UnexpectedEnd);
+        }
+
+        let value = self.bits[self.pos..self.pos + num_bits]
+            .iter()
+            .fold(0, |value, &bit| (value << 1) | bit as u64);
+        self.pos += num_bits;
+        Ok(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+impl Operation {
+    fn from_type_id(type_id: u8) -> Result<Self, PacketError> {
+        match type_id {
+            0 => Ok(Operation::Sum),
+            1 => Ok(Operation::Product),
+            2 => Ok(Operation::Minimum),
+            3 => Ok(Operation::Maximum),
+            5 => Ok(Operation::GreaterThan),
+            6 => Ok(Operation::LessThan),
+            7 => Ok(Operation::EqualTo),
+            _ => Err(PacketError::UnknownOperator(type_id)),
+        }
+    }
+
+    fn accepts_operand_count(&self, count: usize) -> bool {
+        match self {
+            Operation::Sum | Operation::Product | Operation::Minimum | Operation::Maximum => {
+                count >= 1
+            }
+            Operation::GreaterThan | Operation::LessThan | Operation::EqualTo => count == 2,
+        }
+    }
+}
+
+const LITERAL_TYPE_ID: u8 = 4;
+
+#[derive(Debug, PartialEq)]
+enum Payload {
+    Literal(u64),
+    Operator {
+        operation: Operation,
+        sub_packets: Vec<Packet>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Packet {
+    version: u8,
+    payload: Payload,
+}
+impl Packet {
+    fn parse(reader: &mut BitReader) -> Result<Self, PacketError> {
+        let version = reader.read(3)? as u8;
+        let type_id = reader.read(3)? as u8;
+
+        let payload = if type_id == LITERAL_TYPE_ID {
+            Payload::Literal(Packet::parse_literal(reader)?)
+        } else {
+            let operation = Operation::from_type_id(type_id)?;
+            let sub_packets = Packet::parse_sub_packets(reader)?;
+            if !operation.accepts_operand_count(sub_packets.len()) {
+                return Err(PacketError::BadOperandCount(operation, sub_packets.len()));
+            }
+            Payload::Operator {
+                operation,
+                sub_packets,
+            }
+        };
+
+        Ok(Packet { version, payload })
+    }
+
+    // Literal values are split into groups of 4 bits, each prefixed by 1 if more groups follow
+    fn parse_literal(reader: &mut BitReader) -> Result<u64, PacketError> {
+        let mut value = 0;
+        loop {
+            let group = reader.read(5)?;
+            if value >> 60 != 0 {
+                return Err(PacketError::LiteralTooLarge);
+            }
+            value = (value << 4) | (group & 0b1111);
+            if group & 0b10000 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    // Length type 0 gives the total number of bits of the sub-packets,
+    // and length type 1 gives the number of sub-packets
+    fn parse_sub_packets(reader: &mut BitReader) -> Result<Vec<Packet>, PacketError> {
+        let mut sub_packets = Vec::new();
+
+        if reader.read(1)? == 0 {
+            let total_length = reader.read(15)? as usize;
+            let end = reader.pos + total_length;
+            while reader.pos < end {
+                sub_packets.push(Packet::parse(reader)?);
+            }
+            if reader.pos != end {
+                return Err(PacketError::BadSubPacketsLength);
+            }
+        } else {
+            let num_sub_packets = reader.read(11)?;
+            for _ in 0..num_sub_packets {
+                sub_packets.push(Packet::parse(reader)?);
+            }
+        }
+
+        Ok(sub_packets)
+    }
+}
+use std::str::FromStr;
+impl FromStr for Packet {
+    type Err = PacketError;
+
+    // Parses the outermost packet of a transmission, ignoring the padding zeroes after it
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut reader = BitReader::from_hex(s)?;
+        Packet::parse(&mut reader)
+    }
+}
+
+// Part 1
+impl Packet {
+    fn version_sum(&self) -> u64 {
+        let sub_packets_sum = match &self.payload {
+            Payload::Literal(_) => 0,
+            Payload::Operator { sub_packets, .. } => {
+                sub_packets.iter().map(|p| p.version_sum()).sum()
+            }
+        };
+        self.version as u64 + sub_packets_sum
+    }
+}
+
+// Part 2
+impl Packet {
+    // Operand counts were checked when parsing, so every operation has valid operands here
+    fn evaluate(&self) -> Result<u64, PacketError> {
+        let (operation, sub_packets) = match &self.payload {
+            Payload::Literal(value) => return Ok(*value),
+            Payload::Operator {
+                operation,
+                sub_packets,
+            } => (operation, sub_packets),
+        };
+
+        let operands = sub_packets
+            .iter()
+            .map(|p| p.evaluate())
+            .collect::<Result<Vec<u64>, PacketError>>()?;
+        let value = match operation {
+            Operation::Sum => operands
+                .iter()
+                .try_fold(0u64, |total, &operand| total.checked_add(operand))
+                .ok_or(PacketError::Overflow)?,
+            Operation::Product => operands
+                .iter()
+                .try_fold(1u64, |total, &operand| total.checked_mul(operand))
+                .ok_or(PacketError::Overflow)?,
+            Operation::Minimum => operands.iter().copied().min().unwrap_or(0),
+            Operation::Maximum => operands.iter().copied().max().unwrap_or(0),
+            Operation::GreaterThan => (operands[0] > operands[1]) as u64,
+            Operation::LessThan => (operands[0] < operands[1]) as u64,
+            Operation::EqualTo => (operands[0] == operands[1]) as u64,
+        };
+        Ok(value)
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_literal() {
+        assert_eq!(
+            Packet {
+                version: 6,
+                payload: Payload::Literal(2021)
+            },
+            Packet::from_str("D2FE28").unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_operator_with_length_type_0() {
+        assert_eq!(
+            Packet {
+                version: 1,
+                payload: Payload::Operator {
+                    operation: Operation::LessThan,
+                    sub_packets: vec![
+                        Packet {
+                            version: 6,
+                            payload: Payload::Literal(10)
+                        },
+                        Packet {
+                            version: 2,
+                            payload: Payload::Literal(20)
+                        },
+                    ]
+                }
+            },
+            Packet::from_str("38006F45291200").unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_operator_with_length_type_1() {
+        assert_eq!(
+            Packet {
+                version: 7,
+                payload: Payload::Operator {
+                    operation: Operation::Maximum,
+                    sub_packets: vec![
+                        Packet {
+                            version: 2,
+                            payload: Payload::Literal(1)
+                        },
+                        Packet {
+                            version: 4,
+                            payload: Payload::Literal(2)
+                        },
+                        Packet {
+                            version: 1,
+                            payload: Payload::Literal(3)
+                        },
+                    ]
+                }
+            },
+            Packet::from_str("EE00D40C823060").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_transmissions() {
+        assert_eq!(
+            Err(PacketError::InvalidHex('Z')),
+            Packet::from_str("D2ZE28")
+        );
+        assert_eq!(Err(PacketError::UnexpectedEnd), Packet::from_str(""));
+        assert_eq!(Err(PacketError::UnexpectedEnd), Packet::from_str("D2FE"));
+        assert_eq!(
+            Err(PacketError::UnexpectedEnd),
+            Packet::from_str("38006F452912")
+        );
+        assert_eq!(
+            Err(PacketError::LiteralTooLarge),
+            Packet::from_str("D3FFFFFFFFFFFFFFFFFFFFEF")
+        );
+        // a maximum operator with no sub-packets
+        assert_eq!(
+            Err(PacketError::BadOperandCount(Operation::Maximum, 0)),
+            Packet::from_str("0E000000")
+        );
+        // a sum operator announcing 10 bits of sub-packets, followed by an 11 bit literal
+        assert_eq!(
+            Err(PacketError::BadSubPacketsLength),
+            Packet::from_str("000028450")
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let version_sum = |s: &str| Packet::from_str(s).unwrap().version_sum();
+
+        assert_eq!(6, version_sum("D2FE28"));
+        assert_eq!(16, version_sum("8A004A801A8002F478"));
+        assert_eq!(12, version_sum("620080001611562C8802118E34"));
+        assert_eq!(23, version_sum("C0015000016115A2E0802F182340"));
+        assert_eq!(31, version_sum("A0016C880162017C3686B18A3D4780"));
+    }
+
+    #[test]
+    fn part_2() {
+        let evaluate = |s: &str| Packet::from_str(s).unwrap().evaluate().unwrap();
+
+        assert_eq!(3, evaluate("C200B40A82"));
+        assert_eq!(54, evaluate("04005AC33890"));
+        assert_eq!(7, evaluate("880086C3E88112"));
+        assert_eq!(9, evaluate("CE00C43D881120"));
+        assert_eq!(1, evaluate("D8005AC2A8F0"));
+        assert_eq!(0, evaluate("F600BC2D8F"));
+        assert_eq!(0, evaluate("9C005AC2F8F0"));
+        assert_eq!(1, evaluate("9C0141080250320F1802104A08"));
+    }
+
+    #[test]
+    fn rejects_overflowing_values() {
+        // product(2^40, 2^40)
+        let packet = Packet::from_str("0600848C210842108400246108421084200").unwrap();
+
+        assert_eq!(Err(PacketError::Overflow), packet.evaluate());
+    }
 }
@@ -1,5 +1,271 @@
-use super::{Day17, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day17;
+pub const META: DayMeta = DayMeta {
+    day: 17,
+    title: "Trick Shot",
+};
 
 impl DaySolution for Day17 {
-    // TODO
+    type Input = TargetArea;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(TargetArea::from_str(input)?)
+    }
+
+    fn part_1(&self, target: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match find_hits(target)?.iter().map(|&(_, vy)| apex(vy)).max() {
+            Some(highest) => Ok(highest.to_string()),
+            None => Err(Box::new(TrickShotError::Unreachable)),
+        }
+    }
+
+    fn part_2(&self, target: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(find_hits(target)?.len().to_string())
+    }
+}
+
+// Shared
+#[derive(Debug, PartialEq)]
+pub struct TargetArea {
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+}
+impl TargetArea {
+    fn contains_x(&self, x: i64) -> bool {
+        self.min_x <= x && x <= self.max_x
+    }
+
+    fn contains_y(&self, y: i64) -> bool {
+        self.min_y <= y && y <= self.max_y
+    }
+}
+use std::str::FromStr;
+impl FromStr for TargetArea {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad_target = || format!("Bad target area: {}", s.trim());
+        let parse_range = |range: &str, axis: &str| -> Result<(i64, i64), String> {
+            let (a, b) = range
+                .trim()
+                .strip_prefix(axis)
+                .and_then(|r| r.split_once(".."))
+                .ok_or_else(bad_target)?;
+            let a: i64 = a.parse().map_err(|_| bad_target())?;
+            let b: i64 = b.parse().map_err(|_| bad_target())?;
+            Ok((a.min(b), a.max(b)))
+        };
+
+        let (x_range, y_range) = s
+            .trim()
+            .strip_prefix("target area:")
+            .and_then(|ranges| ranges.split_once(','))
+            .ok_or_else(bad_target)?;
+        let (min_x, max_x) = parse_range(x_range, "x=")?;
+        let (min_y, max_y) = parse_range(y_range, "y=")?;
+
+        Ok(TargetArea {
+            min_x,
+            max_x,
+            min_y,
+            max_y,
+        })
+    }
+}
+
+// Closed-form position after n steps, given the initial velocity.
+// Vertically, velocity decreases by 1 every step, forever.
+fn y_at_step(vy: i64, n: i64) -> i64 {
+    n * vy - n * (n - 1) / 2
+}
+
+// Horizontally, drag brings the velocity towards 0, where it then stays
+fn x_at_step(vx: i64, n: i64) -> i64 {
+    let moving_steps = n.min(vx.abs());
+    vx.signum() * (moving_steps * vx.abs() - moving_steps * (moving_steps - 1) / 2)
+}
+
+fn apex(vy: i64) -> i64 {
+    if vy > 0 {
+        vy * (vy + 1) / 2
+    } else {
+        0
+    }
+}
+
+// Steps at which a probe launched with this vertical velocity is inside the target's rows
+fn steps_inside_rows(target: &TargetArea, vy: i64) -> Vec<i64> {
+    let mut steps = Vec::new();
+    let mut n = 1;
+    loop {
+        let y = y_at_step(vy, n);
+        // once the probe is falling (step > vy) it only goes further down
+        if y < target.min_y && n > vy {
+            return steps;
+        }
+        if target.contains_y(y) {
+            steps.push(n);
+        }
+        n += 1;
+    }
+}
+
+// Every initial velocity that puts the probe inside the target area after some step.
+//  - horizontally, any velocity further than the target's far edge overshoots it on the first step
+//  - vertically, a probe launched upwards with vy comes back down through y = 0 with velocity -vy - 1,
+//    so it skips over any row further away than vy, whether it is above or below the origin.
+//    If the target contains y = 0 the probe always comes back to it, but once vy is past the
+//    target's columns the probe has stopped moving horizontally by then, and stopped outside the
+//    target (otherwise every vy would be a hit).
+fn find_hits(target: &TargetArea) -> Result<Vec<(i64, i64)>, TrickShotError> {
+    let min_vx = target.min_x.min(0);
+    let max_vx = target.max_x.max(0);
+    let min_vy = target.min_y.min(0);
+    let mut max_vy = target.min_y.abs().max(target.max_y.abs());
+
+    if target.contains_y(0) {
+        let stops_inside_target =
+            (min_vx..=max_vx).any(|vx| target.contains_x(x_at_step(vx, vx.abs())));
+        if stops_inside_target {
+            return Err(TrickShotError::InfinitelyManyHits);
+        }
+        max_vy = max_vy.max(target.min_x.abs().max(target.max_x.abs()));
+    }
+
+    let mut hits = Vec::new();
+    for vy in min_vy..=max_vy {
+        let steps = steps_inside_rows(target, vy);
+        if steps.is_empty() {
+            continue;
+        }
+
+        for vx in min_vx..=max_vx {
+            if steps.iter().any(|&n| target.contains_x(x_at_step(vx, n))) {
+                hits.push((vx, vy));
+            }
+        }
+    }
+    Ok(hits)
+}
+
+use std::fmt;
+#[derive(Debug, PartialEq)]
+pub enum TrickShotError {
+    Unreachable,
+    // A probe can stop moving horizontally inside the target, and fall back through it from any height
+    InfinitelyManyHits,
+}
+impl fmt::Display for TrickShotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrickShotError::Unreachable => write!(f, "No initial velocity hits the target area"),
+            TrickShotError::InfinitelyManyHits => {
+                write!(f, "Infinitely many initial velocities hit the target area")
+            }
+        }
+    }
+}
+impl Error for TrickShotError {}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    // Simulates every velocity in a fixed box, step by step
+    fn brute_force_hits(target: &TargetArea) -> Vec<(i64, i64)> {
+        let mut hits = Vec::new();
+        for vx in -60..=60 {
+            for vy in -60..=60 {
+                let (mut x, mut y, mut dx, mut dy): (i64, i64, i64, i64) = (0, 0, vx, vy);
+                for _ in 0..200 {
+                    x += dx;
+                    y += dy;
+                    dx -= dx.signum();
+                    dy -= 1;
+                    if target.contains_x(x) && target.contains_y(y) {
+                        hits.push((vx, vy));
+                        break;
+                    }
+                }
+            }
+        }
+        hits.sort_unstable();
+        hits
+    }
+
+    #[test]
+    fn parses_target_area() {
+        assert_eq!(
+            TargetArea {
+                min_x: 20,
+                max_x: 30,
+                min_y: -10,
+                max_y: -5
+            },
+            TargetArea::from_str(EXAMPLE).unwrap()
+        );
+        assert!(TargetArea::from_str("target area: x=20..30").is_err());
+    }
+
+    #[test]
+    fn computes_positions() {
+        let xs: Vec<i64> = (1..=8).map(|n| x_at_step(7, n)).collect();
+        let ys: Vec<i64> = (1..=6).map(|n| y_at_step(2, n)).collect();
+
+        assert_eq!(vec![7, 13, 18, 22, 25, 27, 28, 28], xs);
+        assert_eq!(-28, x_at_step(-7, 10));
+        assert_eq!(vec![2, 3, 3, 2, 0, -3], ys);
+    }
+
+    #[test]
+    fn part_1() {
+        let target = TargetArea::from_str(EXAMPLE).unwrap();
+        let highest = find_hits(&target)
+            .unwrap()
+            .iter()
+            .map(|&(_, vy)| apex(vy))
+            .max();
+
+        assert_eq!(Some(45), highest);
+    }
+
+    #[test]
+    fn part_2() {
+        let target = TargetArea::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(112, find_hits(&target).unwrap().len());
+    }
+
+    #[test]
+    fn handles_targets_anywhere() {
+        let targets = [
+            EXAMPLE,
+            "target area: x=-30..-20, y=-10..-5",
+            "target area: x=20..30, y=5..10",
+            "target area: x=-5..5, y=-10..-5",
+            "target area: x=29..35, y=-3..4",
+        ];
+
+        for target in targets {
+            let target = TargetArea::from_str(target).unwrap();
+            let mut hits = find_hits(&target).unwrap();
+            hits.sort_unstable();
+
+            assert_eq!(brute_force_hits(&target), hits);
+        }
+    }
+
+    #[test]
+    fn detects_infinitely_many_hits() {
+        let target = TargetArea::from_str("target area: x=-30..-20, y=-3..4").unwrap();
+
+        assert_eq!(Err(TrickShotError::InfinitelyManyHits), find_hits(&target));
+    }
 }
@@ -1,5 +1,409 @@
-use super::{Day18, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day18;
+pub const META: DayMeta = DayMeta {
+    day: 18,
+    title: "Snailfish",
+};
 
 impl DaySolution for Day18 {
-    // TODO
+    type Input = Vec<SnailfishNumber>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_homework(input)?)
+    }
+
+    fn part_1(&self, numbers: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match sum_all(numbers.clone()) {
+            Some(total) => Ok(total.magnitude().to_string()),
+            None => Err(Box::new(EmptyHomework)),
+        }
+    }
+
+    fn part_2(&self, numbers: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match largest_pair_magnitude(numbers) {
+            Some(magnitude) => Ok(magnitude.to_string()),
+            None => Err(Box::new(EmptyHomework)),
+        }
+    }
+}
+
+// Shared
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnailfishNumber {
+    Regular(u32),
+    Pair(Box<SnailfishNumber>, Box<SnailfishNumber>),
+}
+impl SnailfishNumber {
+    fn pair(left: SnailfishNumber, right: SnailfishNumber) -> Self {
+        SnailfishNumber::Pair(Box::new(left), Box::new(right))
+    }
+
+    fn magnitude(&self) -> u32 {
+        match self {
+            SnailfishNumber::Regular(value) => *value,
+            SnailfishNumber::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    // Keeps exploding and splitting until neither applies, always exploding first
+    fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    // Explodes the leftmost pair nested inside four pairs, returning whether anything exploded
+    fn explode(&mut self) -> bool {
+        self.explode_at_depth(0).is_some()
+    }
+
+    // When a pair explodes, its values still need to be added to the closest regular numbers on
+    // each side, which are found while going back up the tree.
+    // Returns the values that were not added yet, if anything exploded.
+    fn explode_at_depth(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let (left, right) = match self {
+            SnailfishNumber::Regular(_) => return None,
+            SnailfishNumber::Pair(left, right) => (left, right),
+        };
+
+        if depth >= 4 {
+            if let (SnailfishNumber::Regular(l), SnailfishNumber::Regular(r)) =
+                (left.as_ref(), right.as_ref())
+            {
+                let carry = (Some(*l), Some(*r));
+                *self = SnailfishNumber::Regular(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((carry_left, carry_right)) = left.explode_at_depth(depth + 1) {
+            if let Some(value) = carry_right {
+                right.add_to_leftmost(value);
+            }
+            return Some((carry_left, None));
+        }
+
+        if let Some((carry_left, carry_right)) = right.explode_at_depth(depth + 1) {
+            if let Some(value) = carry_left {
+                left.add_to_rightmost(value);
+            }
+            return Some((None, carry_right));
+        }
+
+        None
+    }
+
+    fn add_to_leftmost(&mut self, value: u32) {
+        match self {
+            SnailfishNumber::Regular(n) => *n += value,
+            SnailfishNumber::Pair(left, _) => left.add_to_leftmost(value),
+        }
+    }
+
+    fn add_to_rightmost(&mut self, value: u32) {
+        match self {
+            SnailfishNumber::Regular(n) => *n += value,
+            SnailfishNumber::Pair(_, right) => right.add_to_rightmost(value),
+        }
+    }
+
+    // Splits the leftmost regular number that is 10 or greater, returning whether anything split
+    fn split(&mut self) -> bool {
+        match self {
+            SnailfishNumber::Regular(n) if *n >= 10 => {
+                *self = SnailfishNumber::pair(
+                    SnailfishNumber::Regular(*n / 2),
+                    SnailfishNumber::Regular(n.div_ceil(2)),
+                );
+                true
+            }
+            SnailfishNumber::Regular(_) => false,
+            SnailfishNumber::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+}
+
+use std::ops::Add;
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, other: SnailfishNumber) -> SnailfishNumber {
+        let mut sum = SnailfishNumber::pair(self, other);
+        sum.reduce();
+        sum
+    }
+}
+
+use std::fmt;
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnailfishNumber::Regular(value) => write!(f, "{}", value),
+            SnailfishNumber::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for SnailfishNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        let mut pos = 0;
+        let number = parse_number(&chars, &mut pos)?;
+
+        if pos != chars.len() {
+            return Err(format!("Unexpected characters after number: {}", s));
+        }
+        Ok(number)
+    }
+}
+
+fn parse_number(chars: &[char], pos: &mut usize) -> Result<SnailfishNumber, String> {
+    let expect = |expected: char, pos: &mut usize| -> Result<(), String> {
+        match chars.get(*pos) {
+            Some(&c) if c == expected => {
+                *pos += 1;
+                Ok(())
+            }
+            Some(&c) => Err(format!("Expected '{}' but found '{}'", expected, c)),
+            None => Err(format!("Expected '{}' but the number ended", expected)),
+        }
+    };
+
+    match chars.get(*pos) {
+        Some('[') => {
+            expect('[', pos)?;
+            let left = parse_number(chars, pos)?;
+            expect(',', pos)?;
+            let right = parse_number(chars, pos)?;
+            expect(']', pos)?;
+            Ok(SnailfishNumber::pair(left, right))
+        }
+        Some(c) if c.is_ascii_digit() => {
+            let mut value: u32 = 0;
+            while let Some(digit) = chars.get(*pos).and_then(|c| c.to_digit(10)) {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or_else(|| String::from("Regular number too large"))?;
+                *pos += 1;
+            }
+            Ok(SnailfishNumber::Regular(value))
+        }
+        Some(c) => Err(format!("Unexpected character '{}'", c)),
+        None => Err(String::from("Number ended unexpectedly")),
+    }
+}
+
+#[derive(Debug)]
+pub struct EmptyHomework;
+impl fmt::Display for EmptyHomework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The homework does not have any snailfish numbers")
+    }
+}
+impl Error for EmptyHomework {}
+
+fn parse_homework(contents: &str) -> Result<Vec<SnailfishNumber>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(SnailfishNumber::from_str)
+        .collect()
+}
+
+// Part 1
+fn sum_all(numbers: Vec<SnailfishNumber>) -> Option<SnailfishNumber> {
+    numbers.into_iter().reduce(|sum, n| sum + n)
+}
+
+// Part 2
+// Snailfish addition is not commutative, so both orders of every pair are tried
+fn largest_pair_magnitude(numbers: &[SnailfishNumber]) -> Option<u32> {
+    let mut largest = None;
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let magnitude = (a.clone() + b.clone()).magnitude();
+            largest = largest.max(Some(magnitude));
+        }
+    }
+    largest
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOMEWORK: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    fn number(s: &str) -> SnailfishNumber {
+        SnailfishNumber::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let s = "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]";
+
+        assert_eq!(s, number(s).to_string());
+        assert_eq!(
+            SnailfishNumber::pair(SnailfishNumber::Regular(1), SnailfishNumber::Regular(2)),
+            number("[1,2]")
+        );
+        assert!(SnailfishNumber::from_str("[1,2").is_err());
+        assert!(SnailfishNumber::from_str("[1;2]").is_err());
+        assert!(SnailfishNumber::from_str("[1,2]]").is_err());
+        assert!(SnailfishNumber::from_str("[99999999999,1]").is_err());
+    }
+
+    #[test]
+    fn explodes() {
+        let examples = [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ];
+
+        for (before, after) in examples {
+            let mut n = number(before);
+            assert!(n.explode());
+            assert_eq!(after, n.to_string());
+        }
+        assert!(!number("[[1,2],3]").explode());
+    }
+
+    #[test]
+    fn splits() {
+        let mut n = SnailfishNumber::Regular(10);
+        assert!(n.split());
+        assert_eq!("[5,5]", n.to_string());
+
+        let mut n = SnailfishNumber::Regular(11);
+        assert!(n.split());
+        assert_eq!("[5,6]", n.to_string());
+
+        let mut n = number("[12,13]");
+        assert!(n.split());
+        assert_eq!("[[6,6],13]", n.to_string());
+        assert!(!number("[9,[1,2]]").split());
+    }
+
+    #[test]
+    fn adds_and_reduces() {
+        let mut n = number("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+
+        assert!(n.explode());
+        assert_eq!("[[[[0,7],4],[7,[[8,4],9]]],[1,1]]", n.to_string());
+        assert!(n.explode());
+        assert_eq!("[[[[0,7],4],[15,[0,13]]],[1,1]]", n.to_string());
+        assert!(!n.explode() && n.split());
+        assert_eq!("[[[[0,7],4],[[7,8],[0,13]]],[1,1]]", n.to_string());
+        assert!(!n.explode() && n.split());
+        assert_eq!("[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]", n.to_string());
+        assert!(n.explode());
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", n.to_string());
+
+        assert_eq!(
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            (number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]")).to_string()
+        );
+    }
+
+    #[test]
+    fn sums_lists() {
+        let list = |n: u32| {
+            (1..=n)
+                .map(|i| {
+                    SnailfishNumber::pair(SnailfishNumber::Regular(i), SnailfishNumber::Regular(i))
+                })
+                .collect::<Vec<SnailfishNumber>>()
+        };
+
+        assert_eq!(
+            "[[[[1,1],[2,2]],[3,3]],[4,4]]",
+            sum_all(list(4)).unwrap().to_string()
+        );
+        assert_eq!(
+            "[[[[3,0],[5,3]],[4,4]],[5,5]]",
+            sum_all(list(5)).unwrap().to_string()
+        );
+        assert_eq!(
+            "[[[[5,0],[7,4]],[5,5]],[6,6]]",
+            sum_all(list(6)).unwrap().to_string()
+        );
+
+        let larger_example = parse_homework(
+            "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]",
+        )
+        .unwrap();
+        assert_eq!(
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+            sum_all(larger_example).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn computes_magnitude() {
+        assert_eq!(143, number("[[1,2],[[3,4],5]]").magnitude());
+        assert_eq!(
+            1384,
+            number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").magnitude()
+        );
+        assert_eq!(445, number("[[[[1,1],[2,2]],[3,3]],[4,4]]").magnitude());
+        assert_eq!(791, number("[[[[3,0],[5,3]],[4,4]],[5,5]]").magnitude());
+        assert_eq!(1137, number("[[[[5,0],[7,4]],[5,5]],[6,6]]").magnitude());
+        assert_eq!(
+            3488,
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude()
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let total = sum_all(parse_homework(HOMEWORK).unwrap()).unwrap();
+
+        assert_eq!(
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]",
+            total.to_string()
+        );
+        assert_eq!(4140, total.magnitude());
+    }
+
+    #[test]
+    fn part_2() {
+        let numbers = parse_homework(HOMEWORK).unwrap();
+
+        assert_eq!(Some(3993), largest_pair_magnitude(&numbers));
+    }
 }
@@ -1,5 +1,518 @@
-use super::{Day19, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day19;
+pub const META: DayMeta = DayMeta {
+    day: 19,
+    title: "Beacon Scanner",
+};
 
 impl DaySolution for Day19 {
-    // TODO
+    type Input = ScannerReports;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(ScannerReports::new(parse_scanners(input)?))
+    }
+
+    fn part_1(&self, reports: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let map = reports.beacon_map()?;
+        Ok(map.beacons.len().to_string())
+    }
+
+    fn part_2(&self, reports: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let map = reports.beacon_map()?;
+        Ok(map.largest_scanner_distance().to_string())
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Vec3 {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+impl Vec3 {
+    fn new(x: i32, y: i32, z: i32) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    fn manhattan_distance(&self, other: Vec3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    fn squared_distance(&self, other: Vec3) -> i64 {
+        let d = *self - other;
+        let (x, y, z) = (d.x as i64, d.y as i64, d.z as i64);
+        x * x + y * y + z * z
+    }
+}
+use std::ops::{Add, Sub};
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+use std::str::FromStr;
+impl FromStr for Vec3 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords = s
+            .trim()
+            .split(',')
+            .map(|c| c.parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|_| format!("Bad coordinates: {}", s))?;
+
+        match coords[..] {
+            [x, y, z] => Ok(Vec3::new(x, y, z)),
+            _ => Err(format!("Bad coordinates: {}", s)),
+        }
+    }
+}
+
+// A proper rotation, as a 3x3 matrix where each row has a single non-zero entry of 1 or -1
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rotation([[i32; 3]; 3]);
+impl Rotation {
+    // The 24 ways a scanner can be facing: every permutation of the axes with every combination of
+    // signs, keeping only the ones with determinant 1 (the others are mirror images)
+    fn all() -> Vec<Rotation> {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let mut rotations = Vec::new();
+        for permutation in permutations {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &axis) in permutation.iter().enumerate() {
+                    matrix[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+
+                let rotation = Rotation(matrix);
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+        rotations
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    fn apply(&self, v: Vec3) -> Vec3 {
+        let m = &self.0;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+}
+
+const MIN_OVERLAPPING_BEACONS: usize = 12;
+// Every pair out of 12 shared beacons is at the same distance in both scanners
+const MIN_SHARED_DISTANCES: usize = MIN_OVERLAPPING_BEACONS * (MIN_OVERLAPPING_BEACONS - 1) / 2;
+
+use std::collections::HashMap;
+#[derive(Debug)]
+struct Scanner {
+    beacons: Vec<Vec3>,
+    // How many times each squared distance between two of the beacons shows up.
+    // Distances do not depend on the scanner's position or rotation, so two scanners that see
+    // the same beacons must share these distances.
+    fingerprint: HashMap<i64, usize>,
+}
+impl Scanner {
+    fn new(beacons: Vec<Vec3>) -> Self {
+        let mut fingerprint = HashMap::new();
+        for (i, a) in beacons.iter().enumerate() {
+            for b in &beacons[i + 1..] {
+                *fingerprint.entry(a.squared_distance(*b)).or_insert(0) += 1;
+            }
+        }
+
+        Scanner {
+            beacons,
+            fingerprint,
+        }
+    }
+
+    fn shared_distances(&self, other: &Scanner) -> usize {
+        self.fingerprint
+            .iter()
+            .map(|(distance, &count)| count.min(*other.fingerprint.get(distance).unwrap_or(&0)))
+            .sum()
+    }
+
+    // Finds the rotation and position that make at least 12 of this scanner's beacons land on
+    // the known beacons, and returns the scanner's position and its beacons, both in the known
+    // beacons' coordinates
+    fn align(&self, known_beacons: &[Vec3]) -> Option<(Vec3, Vec<Vec3>)> {
+        for rotation in Rotation::all() {
+            let rotated: Vec<Vec3> = self.beacons.iter().map(|&b| rotation.apply(b)).collect();
+
+            let mut offset_counts: HashMap<Vec3, usize> = HashMap::new();
+            for &known in known_beacons {
+                for &beacon in &rotated {
+                    let count = offset_counts.entry(known - beacon).or_insert(0);
+                    *count += 1;
+
+                    if *count >= MIN_OVERLAPPING_BEACONS {
+                        let position = known - beacon;
+                        let aligned = rotated.iter().map(|&b| b + position).collect();
+                        return Some((position, aligned));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+impl FromStr for Scanner {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let beacons = s
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with("---"))
+            .map(Vec3::from_str)
+            .collect::<Result<Vec<Vec3>, String>>()?;
+
+        Ok(Scanner::new(beacons))
+    }
+}
+
+use std::collections::HashSet;
+// All the beacons and scanners, in the coordinates of the first scanner
+#[derive(Debug)]
+struct BeaconMap {
+    beacons: HashSet<Vec3>,
+    scanner_positions: Vec<Vec3>,
+}
+impl BeaconMap {
+    // Starting from the first scanner, keeps aligning the remaining scanners against the ones
+    // that were already placed, only trying pairs whose fingerprints could share 12 beacons
+    fn assemble(scanners: &[Scanner]) -> Result<Self, UnalignableScanners> {
+        if scanners.is_empty() {
+            return Err(UnalignableScanners(0));
+        }
+
+        let mut placed: Vec<Option<(Vec3, Vec<Vec3>)>> = vec![None; scanners.len()];
+        placed[0] = Some((Vec3::new(0, 0, 0), scanners[0].beacons.clone()));
+        let mut to_align_against = vec![0];
+
+        while let Some(i) = to_align_against.pop() {
+            let known_beacons = placed[i].as_ref().unwrap().1.clone();
+
+            for j in 0..scanners.len() {
+                if placed[j].is_some()
+                    || scanners[i].shared_distances(&scanners[j]) < MIN_SHARED_DISTANCES
+                {
+                    continue;
+                }
+
+                if let Some(alignment) = scanners[j].align(&known_beacons) {
+                    placed[j] = Some(alignment);
+                    to_align_against.push(j);
+                }
+            }
+        }
+
+        let num_unaligned = placed.iter().filter(|p| p.is_none()).count();
+        if num_unaligned > 0 {
+            return Err(UnalignableScanners(num_unaligned));
+        }
+
+        let mut map = BeaconMap {
+            beacons: HashSet::new(),
+            scanner_positions: Vec::new(),
+        };
+        for (position, beacons) in placed.into_iter().flatten() {
+            map.scanner_positions.push(position);
+            map.beacons.extend(beacons);
+        }
+        Ok(map)
+    }
+}
+
+use std::fmt;
+#[derive(Clone, Debug)]
+pub struct UnalignableScanners(usize);
+impl fmt::Display for UnalignableScanners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} scanners could not be aligned with the first scanner",
+            self.0
+        )
+    }
+}
+impl Error for UnalignableScanners {}
+
+use std::cell::OnceCell;
+// Aligning the scanners is most of the work, and both parts need the assembled map,
+// so it is only assembled once, the first time a part asks for it
+pub struct ScannerReports {
+    scanners: Vec<Scanner>,
+    map: OnceCell<Result<BeaconMap, UnalignableScanners>>,
+}
+impl ScannerReports {
+    fn new(scanners: Vec<Scanner>) -> Self {
+        ScannerReports {
+            scanners,
+            map: OnceCell::new(),
+        }
+    }
+
+    fn beacon_map(&self) -> Result<&BeaconMap, UnalignableScanners> {
+        self.map
+            .get_or_init(|| BeaconMap::assemble(&self.scanners))
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+}
+
+fn parse_scanners(contents: &str) -> Result<Vec<Scanner>, String> {
+    contents
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter(|s| !s.trim().is_empty())
+        .map(Scanner::from_str)
+        .collect()
+}
+
+// Part 2
+impl BeaconMap {
+    fn largest_scanner_distance(&self) -> i32 {
+        let positions = &self.scanner_positions;
+        positions
+            .iter()
+            .flat_map(|a| positions.iter().map(move |b| a.manhattan_distance(*b)))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    #[test]
+    fn generates_24_distinct_rotations() {
+        let v = Vec3::new(1, 2, 3);
+        let rotated: HashSet<Vec3> = Rotation::all().iter().map(|r| r.apply(v)).collect();
+
+        assert_eq!(24, Rotation::all().len());
+        assert_eq!(24, rotated.len());
+        assert!(rotated.contains(&Vec3::new(-1, -2, 3)));
+        assert!(!rotated.contains(&Vec3::new(-1, 2, 3)));
+    }
+
+    #[test]
+    fn aligns_overlapping_scanners() {
+        let scanners = parse_scanners(EXAMPLE).unwrap();
+
+        assert!(scanners[0].shared_distances(&scanners[1]) >= MIN_SHARED_DISTANCES);
+        let (position, _) = scanners[1].align(&scanners[0].beacons).unwrap();
+        assert_eq!(Vec3::new(68, -1246, -43), position);
+        assert!(scanners[0].align(&scanners[2].beacons).is_none());
+    }
+
+    #[test]
+    fn part_1() {
+        let scanners = parse_scanners(EXAMPLE).unwrap();
+        let map = BeaconMap::assemble(&scanners).unwrap();
+
+        assert_eq!(
+            vec![
+                Vec3::new(0, 0, 0),
+                Vec3::new(68, -1246, -43),
+                Vec3::new(1105, -1205, 1229),
+                Vec3::new(-92, -2380, -20),
+                Vec3::new(-20, -1133, 1061),
+            ],
+            map.scanner_positions
+        );
+        assert_eq!(79, map.beacons.len());
+    }
+
+    #[test]
+    fn part_2() {
+        let scanners = parse_scanners(EXAMPLE).unwrap();
+        let map = BeaconMap::assemble(&scanners).unwrap();
+
+        assert_eq!(3621, map.largest_scanner_distance());
+    }
+
+    #[test]
+    fn assembles_map_once_for_both_parts() {
+        let reports = Day19.parse(EXAMPLE).unwrap();
+        assert!(reports.map.get().is_none());
+
+        assert_eq!("79", Day19.part_1(&reports).unwrap());
+        assert!(reports.map.get().is_some());
+        assert_eq!("3621", Day19.part_2(&reports).unwrap());
+    }
 }
@@ -1,5 +1,264 @@
-use super::{Day20, DaySolution};
+use super::grid::Grid;
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day20;
+pub const META: DayMeta = DayMeta {
+    day: 20,
+    title: "Trench Map",
+};
 
 impl DaySolution for Day20 {
-    // TODO
+    type Input = (EnhancementAlgorithm, InfiniteImage);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_puzzle(input)?)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (algorithm, image) = input;
+        match image.enhance_times(algorithm, 2).num_lit() {
+            Some(num_lit) => Ok(num_lit.to_string()),
+            None => Err(Box::new(InfinitelyManyLitPixels)),
+        }
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let (algorithm, image) = input;
+        match image.enhance_times(algorithm, 50).num_lit() {
+            Some(num_lit) => Ok(num_lit.to_string()),
+            None => Err(Box::new(InfinitelyManyLitPixels)),
+        }
+    }
+}
+
+// Shared
+const ALGORITHM_LEN: usize = 512;
+
+pub struct EnhancementAlgorithm([bool; ALGORITHM_LEN]);
+use std::str::FromStr;
+impl FromStr for EnhancementAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pixels = parse_pixels(s.trim())?;
+        if pixels.len() != ALGORITHM_LEN {
+            return Err(format!(
+                "Enhancement algorithm must have {} characters, found {}",
+                ALGORITHM_LEN,
+                pixels.len()
+            ));
+        }
+
+        let mut algorithm = [false; ALGORITHM_LEN];
+        algorithm.copy_from_slice(&pixels);
+        Ok(EnhancementAlgorithm(algorithm))
+    }
+}
+
+fn parse_pixels(s: &str) -> Result<Vec<bool>, String> {
+    s.chars()
+        .map(|c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("Unknown pixel '{}'", c)),
+        })
+        .collect()
+}
+
+// An image that goes on forever in every direction.
+// Only the pixels that the input and the enhancements can affect are stored, every other pixel is
+// the same as the background. When the algorithm lights up a pixel surrounded by dark pixels
+// (index 0), the whole background lights up too, and it can then go dark again on the next step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InfiniteImage {
+    pixels: Grid<bool>,
+    background: bool,
+}
+impl InfiniteImage {
+    fn pixel(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+        *self
+            .pixels
+            .get(x as usize, y as usize)
+            .unwrap_or(&self.background)
+    }
+
+    // Every pixel next to the stored ones can change, so the stored image grows by 1 on each side
+    fn enhance(&self, algorithm: &EnhancementAlgorithm) -> InfiniteImage {
+        let width = self.pixels.width() + 2;
+        let height = self.pixels.height() + 2;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                // (x, y) in the new image is (x - 1, y - 1) in this one
+                let mut index = 0;
+                for dy in -2..=0 {
+                    for dx in -2..=0 {
+                        index = (index << 1) | self.pixel(x + dx, y + dy) as usize;
+                    }
+                }
+                pixels.push(algorithm.0[index]);
+            }
+        }
+
+        let background_index = if self.background {
+            ALGORITHM_LEN - 1
+        } else {
+            0
+        };
+        InfiniteImage {
+            pixels: Grid::new(width, height, pixels),
+            background: algorithm.0[background_index],
+        }
+    }
+
+    fn enhance_times(&self, algorithm: &EnhancementAlgorithm, times: usize) -> InfiniteImage {
+        let mut image = self.clone();
+        for _ in 0..times {
+            image = image.enhance(algorithm);
+        }
+        image
+    }
+
+    // There is no finite count while the background is lit
+    fn num_lit(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+
+        Some(
+            self.pixels
+                .positions()
+                .filter(|&(x, y)| *self.pixels.get(x, y).unwrap())
+                .count(),
+        )
+    }
+}
+impl FromStr for InfiniteImage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let width = rows.first().map_or(0, |r| r.len());
+
+        let mut pixels = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            if row.len() != width {
+                return Err(format!("Image row has the wrong width: {}", row));
+            }
+            pixels.extend(parse_pixels(row)?);
+        }
+
+        Ok(InfiniteImage {
+            pixels: Grid::new(width, rows.len(), pixels),
+            background: false,
+        })
+    }
+}
+
+use std::fmt;
+#[derive(Debug)]
+pub struct InfinitelyManyLitPixels;
+impl fmt::Display for InfinitelyManyLitPixels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The enhanced image has infinitely many lit pixels")
+    }
+}
+impl Error for InfinitelyManyLitPixels {}
+
+fn parse_puzzle(contents: &str) -> Result<(EnhancementAlgorithm, InfiniteImage), String> {
+    let contents = contents.replace("\r\n", "\n");
+    let (algorithm_str, image_str) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| String::from("Missing empty line between algorithm and image"))?;
+
+    Ok((
+        EnhancementAlgorithm::from_str(algorithm_str)?,
+        InfiniteImage::from_str(image_str)?,
+    ))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    // Lights up a pixel only when it and all of its neighbours are dark
+    fn flipping_algorithm() -> EnhancementAlgorithm {
+        let mut algorithm = [false; ALGORITHM_LEN];
+        algorithm[0] = true;
+        EnhancementAlgorithm(algorithm)
+    }
+
+    #[test]
+    fn parses_puzzle() {
+        let (algorithm, image) = parse_puzzle(EXAMPLE).unwrap();
+
+        assert!(!algorithm.0[0]);
+        assert!(algorithm.0[34]);
+        assert_eq!(Some(10), image.num_lit());
+        assert!(EnhancementAlgorithm::from_str("#..#").is_err());
+    }
+
+    #[test]
+    fn enhances_example() {
+        let (algorithm, image) = parse_puzzle(EXAMPLE).unwrap();
+
+        assert_eq!(image, image.enhance_times(&algorithm, 0));
+        assert_eq!(Some(24), image.enhance(&algorithm).num_lit());
+    }
+
+    #[test]
+    fn flips_background() {
+        let algorithm = flipping_algorithm();
+        let image = InfiniteImage::from_str("#").unwrap();
+
+        let once = image.enhance(&algorithm);
+        assert!(once.background);
+        assert_eq!(None, once.num_lit());
+        // only the pixels next to the original lit pixel stay dark
+        assert_eq!(
+            InfiniteImage {
+                pixels: Grid::new(3, 3, vec![false; 9]),
+                background: true
+            },
+            once
+        );
+
+        let twice = once.enhance(&algorithm);
+        assert!(!twice.background);
+        assert_eq!(Some(1), twice.num_lit());
+        assert!(twice.pixel(2, 2));
+        assert_eq!(Some(1), image.enhance_times(&algorithm, 50).num_lit());
+    }
+
+    #[test]
+    fn part_1() {
+        let (algorithm, image) = parse_puzzle(EXAMPLE).unwrap();
+
+        assert_eq!(Some(35), image.enhance_times(&algorithm, 2).num_lit());
+    }
+
+    #[test]
+    fn part_2() {
+        let (algorithm, image) = parse_puzzle(EXAMPLE).unwrap();
+
+        assert_eq!(Some(3351), image.enhance_times(&algorithm, 50).num_lit());
+    }
 }
@@ -1,5 +1,207 @@
-use super::{Day21, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day21;
+pub const META: DayMeta = DayMeta {
+    day: 21,
+    title: "Dirac Dice",
+};
 
 impl DaySolution for Day21 {
-    // TODO
+    type Input = GameState;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(GameState::from_str(input)?)
+    }
+
+    fn part_1(&self, game: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(play_deterministic(*game).to_string())
+    }
+
+    fn part_2(&self, game: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let wins = count_quantum_wins(*game);
+        Ok(wins.iter().max().unwrap_or(&0).to_string())
+    }
+}
+
+// Shared
+const BOARD_SIZE: u32 = 10;
+const ROLLS_PER_TURN: u32 = 3;
+
+// Positions go from 1 to 10, and `turn` is the index of the player about to move
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GameState {
+    positions: [u32; 2],
+    scores: [u32; 2],
+    turn: usize,
+}
+impl GameState {
+    fn new(start_1: u32, start_2: u32) -> Self {
+        GameState {
+            positions: [start_1, start_2],
+            scores: [0, 0],
+            turn: 0,
+        }
+    }
+
+    // Moves the current player by the total of their rolls, and passes the turn to the other player
+    fn advance(&self, roll_total: u32) -> GameState {
+        let mut next = *self;
+        let player = self.turn;
+
+        next.positions[player] = (self.positions[player] - 1 + roll_total) % BOARD_SIZE + 1;
+        next.scores[player] += next.positions[player];
+        next.turn = 1 - player;
+
+        next
+    }
+
+    fn winner(&self, winning_score: u32) -> Option<usize> {
+        self.scores.iter().position(|&s| s >= winning_score)
+    }
+}
+use std::str::FromStr;
+impl FromStr for GameState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let starts = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                l.rsplit(':')
+                    .next()
+                    .and_then(|p| p.trim().parse::<u32>().ok())
+                    .filter(|p| (1..=BOARD_SIZE).contains(p))
+                    .ok_or_else(|| format!("Bad starting position: {}", l))
+            })
+            .collect::<Result<Vec<u32>, String>>()?;
+
+        match starts[..] {
+            [start_1, start_2] => Ok(GameState::new(start_1, start_2)),
+            _ => Err(format!("Expected 2 players, found {}", starts.len())),
+        }
+    }
+}
+
+// Part 1
+const DETERMINISTIC_WINNING_SCORE: u32 = 1000;
+
+struct DeterministicDie {
+    next_value: u32,
+    num_rolls: u32,
+}
+impl DeterministicDie {
+    fn new() -> Self {
+        DeterministicDie {
+            next_value: 1,
+            num_rolls: 0,
+        }
+    }
+
+    fn roll(&mut self) -> u32 {
+        let value = self.next_value;
+        self.next_value = self.next_value % 100 + 1;
+        self.num_rolls += 1;
+        value
+    }
+}
+
+// Returns the losing player's score multiplied by the number of times the die was rolled
+fn play_deterministic(mut game: GameState) -> u32 {
+    let mut die = DeterministicDie::new();
+
+    while game.winner(DETERMINISTIC_WINNING_SCORE).is_none() {
+        let roll_total = (0..ROLLS_PER_TURN).map(|_| die.roll()).sum();
+        game = game.advance(roll_total);
+    }
+
+    let losing_score = game.scores.iter().min().unwrap();
+    losing_score * die.num_rolls
+}
+
+// Part 2
+use std::collections::HashMap;
+const DIRAC_WINNING_SCORE: u32 = 21;
+
+// Rolling the 3-sided die 3 times splits the universe 27 times, but there are only 7 different
+// totals, each reached in this many universes
+const DIRAC_ROLL_TOTALS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+// Returns in how many universes each player wins
+fn count_quantum_wins(game: GameState) -> [u64; 2] {
+    let mut memo = HashMap::new();
+    count_quantum_wins_from(game, &mut memo)
+}
+
+fn count_quantum_wins_from(game: GameState, memo: &mut HashMap<GameState, [u64; 2]>) -> [u64; 2] {
+    if let Some(winner) = game.winner(DIRAC_WINNING_SCORE) {
+        let mut wins = [0, 0];
+        wins[winner] = 1;
+        return wins;
+    }
+    if let Some(&wins) = memo.get(&game) {
+        return wins;
+    }
+
+    let mut wins = [0, 0];
+    for (roll_total, num_universes) in DIRAC_ROLL_TOTALS {
+        let sub_wins = count_quantum_wins_from(game.advance(roll_total), memo);
+        wins[0] += sub_wins[0] * num_universes;
+        wins[1] += sub_wins[1] * num_universes;
+    }
+
+    memo.insert(game, wins);
+    wins
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn parses_starting_positions() {
+        assert_eq!(GameState::new(4, 8), GameState::from_str(EXAMPLE).unwrap());
+        assert!(GameState::from_str("Player 1 starting position: 4").is_err());
+        assert!(GameState::from_str(
+            "Player 1 starting position: 11\nPlayer 2 starting position: 8"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn advances_turns() {
+        let game = GameState::new(4, 8);
+
+        let game = game.advance(1 + 2 + 3);
+        assert_eq!([10, 8], game.positions);
+        assert_eq!([10, 0], game.scores);
+        assert_eq!(1, game.turn);
+
+        let game = game.advance(4 + 5 + 6);
+        assert_eq!([10, 3], game.positions);
+        assert_eq!([10, 3], game.scores);
+
+        let game = game.advance(7 + 8 + 9);
+        assert_eq!([4, 3], game.positions);
+        assert_eq!([14, 3], game.scores);
+        assert_eq!(1, game.turn);
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(739785, play_deterministic(GameState::new(4, 8)));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(
+            [444356092776315, 341960390180808],
+            count_quantum_wins(GameState::new(4, 8))
+        );
+    }
 }
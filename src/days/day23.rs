@@ -1,5 +1,417 @@
-use super::{Day23, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day23;
+pub const META: DayMeta = DayMeta {
+    day: 23,
+    title: "Amphipod",
+};
 
 impl DaySolution for Day23 {
-    // TODO
+    type Input = Burrow;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Burrow::from_str(input)?)
+    }
+
+    fn part_1(&self, burrow: &Self::Input) -> Result<String, Box<dyn Error>> {
+        match lowest_organizing_energy(burrow) {
+            Some(energy) => Ok(energy.to_string()),
+            None => Err(Box::new(CannotOrganize)),
+        }
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let burrow = input.unfold()?;
+        match lowest_organizing_energy(&burrow) {
+            Some(energy) => Ok(energy.to_string()),
+            None => Err(Box::new(CannotOrganize)),
+        }
+    }
+}
+
+// Shared
+const HALLWAY_LEN: usize = 11;
+const NUM_ROOMS: usize = 4;
+const MAX_ROOM_DEPTH: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+impl Amphipod {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Amphipod::Amber),
+            'B' => Some(Amphipod::Bronze),
+            'C' => Some(Amphipod::Copper),
+            'D' => Some(Amphipod::Desert),
+            _ => None,
+        }
+    }
+
+    fn energy_per_step(&self) -> u32 {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    // Rooms are numbered from left to right
+    fn home_room(&self) -> usize {
+        *self as usize
+    }
+}
+
+// Hallway position right outside a room, where amphipods can never stop
+fn room_entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn is_room_entrance(x: usize) -> bool {
+    (2..=room_entrance(NUM_ROOMS - 1)).contains(&x) && x.is_multiple_of(2)
+}
+
+// Slot 0 of a room is the one next to the hallway, and only the first `depth` slots of each room
+// are used, so that both the folded and unfolded burrows fit in the same type
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_LEN],
+    rooms: [[Option<Amphipod>; MAX_ROOM_DEPTH]; NUM_ROOMS],
+    depth: usize,
+}
+impl Burrow {
+    fn is_organized(&self) -> bool {
+        self.hallway.iter().all(|cell| cell.is_none())
+            && (0..NUM_ROOMS).all(|room| {
+                self.rooms[room][..self.depth]
+                    .iter()
+                    .all(|cell| cell.map(|a| a.home_room()) == Some(room))
+            })
+    }
+
+    // An amphipod only enters its own room, and only when no other kind of amphipod is in there
+    fn room_accepts_arrivals(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .all(|cell| cell.is_none_or(|a| a.home_room() == room))
+    }
+
+    // Whether every hallway position on the way from `from` to `to` is free, not counting `from`
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let (first, last) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+        self.hallway[first..=last].iter().all(|cell| cell.is_none())
+    }
+
+    // Every burrow reachable with a single move, along with the energy that move costs.
+    // Amphipods either leave a room for a hallway position, or go from the hallway into their room.
+    fn moves(&self) -> Vec<(Burrow, u32)> {
+        let mut moves = Vec::new();
+
+        for x in 0..HALLWAY_LEN {
+            let amphipod = match self.hallway[x] {
+                Some(amphipod) => amphipod,
+                None => continue,
+            };
+            let room = amphipod.home_room();
+            let entrance = room_entrance(room);
+            if !self.room_accepts_arrivals(room) || !self.hallway_clear(x, entrance) {
+                continue;
+            }
+            let slot = match (0..self.depth)
+                .rev()
+                .find(|&s| self.rooms[room][s].is_none())
+            {
+                Some(slot) => slot,
+                None => continue,
+            };
+
+            let mut next = self.clone();
+            next.hallway[x] = None;
+            next.rooms[room][slot] = Some(amphipod);
+            let steps = (x.abs_diff(entrance) + slot + 1) as u32;
+            // going home right away is never worse than doing anything else first
+            return vec![(next, steps * amphipod.energy_per_step())];
+        }
+
+        for room in 0..NUM_ROOMS {
+            if self.room_accepts_arrivals(room) {
+                // everyone in there is already home
+                continue;
+            }
+            let (slot, amphipod) =
+                match (0..self.depth).find_map(|s| self.rooms[room][s].map(|a| (s, a))) {
+                    Some(top) => top,
+                    None => continue,
+                };
+
+            let entrance = room_entrance(room);
+            for x in 0..HALLWAY_LEN {
+                if is_room_entrance(x) || !self.hallway_clear(entrance, x) {
+                    continue;
+                }
+
+                let mut next = self.clone();
+                next.rooms[room][slot] = None;
+                next.hallway[x] = Some(amphipod);
+                let steps = (slot + 1 + x.abs_diff(entrance)) as u32;
+                moves.push((next, steps * amphipod.energy_per_step()));
+            }
+        }
+
+        moves
+    }
+}
+use std::str::FromStr;
+impl FromStr for Burrow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_cell = |c: char| match c {
+            '.' => Ok(None),
+            _ => Amphipod::from_char(c)
+                .map(Some)
+                .ok_or_else(|| format!("Unknown amphipod '{}'", c)),
+        };
+        let is_cell = |c: &char| *c == '.' || c.is_ascii_alphabetic();
+
+        let mut lines = s.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let hallway_line = lines.nth(1).ok_or("Missing hallway")?;
+        let hallway_cells: Vec<char> = hallway_line.chars().filter(is_cell).collect();
+        if hallway_cells.len() != HALLWAY_LEN {
+            return Err(format!("Bad hallway: {}", hallway_line));
+        }
+
+        let mut burrow = Burrow {
+            hallway: [None; HALLWAY_LEN],
+            rooms: [[None; MAX_ROOM_DEPTH]; NUM_ROOMS],
+            depth: 0,
+        };
+        for (x, &c) in hallway_cells.iter().enumerate() {
+            burrow.hallway[x] = parse_cell(c)?;
+        }
+
+        // the line closing the rooms has no cells
+        for line in lines {
+            let cells: Vec<char> = line.chars().filter(is_cell).collect();
+            if cells.is_empty() {
+                continue;
+            }
+            if cells.len() != NUM_ROOMS || burrow.depth == MAX_ROOM_DEPTH {
+                return Err(format!("Bad room row: {}", line));
+            }
+            for (room, &c) in cells.iter().enumerate() {
+                burrow.rooms[room][burrow.depth] = parse_cell(c)?;
+            }
+            burrow.depth += 1;
+        }
+
+        if burrow.depth == 0 {
+            return Err(String::from("Missing rooms"));
+        }
+        Ok(burrow)
+    }
+}
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+// Dijkstra's algorithm over every configuration of the burrow
+fn lowest_organizing_energy(start: &Burrow) -> Option<u32> {
+    let mut best_energy = HashMap::new();
+    let mut to_visit = BinaryHeap::new();
+    best_energy.insert(start.clone(), 0);
+    to_visit.push(Reverse((0, start.clone())));
+
+    while let Some(Reverse((energy, burrow))) = to_visit.pop() {
+        if burrow.is_organized() {
+            return Some(energy);
+        }
+        if energy > best_energy[&burrow] {
+            // a cheaper way to this configuration was already found
+            continue;
+        }
+
+        for (next, cost) in burrow.moves() {
+            let next_energy = energy + cost;
+            if next_energy < *best_energy.get(&next).unwrap_or(&u32::MAX) {
+                best_energy.insert(next.clone(), next_energy);
+                to_visit.push(Reverse((next_energy, next)));
+            }
+        }
+    }
+
+    None
+}
+
+use std::fmt;
+#[derive(Debug)]
+pub struct CannotOrganize;
+impl fmt::Display for CannotOrganize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The amphipods cannot be organized")
+    }
+}
+impl Error for CannotOrganize {}
+
+// Part 2
+// The folded part of the diagram, which goes between the two rows of the original one
+const FOLDED_ROWS: [[Amphipod; NUM_ROOMS]; 2] = [
+    [
+        Amphipod::Desert,
+        Amphipod::Copper,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+    ],
+    [
+        Amphipod::Desert,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+        Amphipod::Copper,
+    ],
+];
+
+impl Burrow {
+    fn unfold(&self) -> Result<Burrow, String> {
+        if self.depth != 2 {
+            return Err(format!(
+                "Only burrows with rooms of depth 2 can be unfolded, found depth {}",
+                self.depth
+            ));
+        }
+
+        let mut unfolded = self.clone();
+        unfolded.depth = 2 + FOLDED_ROWS.len();
+        for (room, cells) in unfolded.rooms.iter_mut().enumerate() {
+            *cells = [
+                self.rooms[room][0],
+                Some(FOLDED_ROWS[0][room]),
+                Some(FOLDED_ROWS[1][room]),
+                self.rooms[room][1],
+            ];
+        }
+        Ok(unfolded)
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    const UNFOLDED_EXAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn parses_burrow() {
+        let burrow = Burrow::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(2, burrow.depth);
+        assert_eq!([None; HALLWAY_LEN], burrow.hallway);
+        assert_eq!(
+            [Some(Amphipod::Bronze), Some(Amphipod::Amber), None, None],
+            burrow.rooms[0]
+        );
+        assert!(Burrow::from_str("#############\n#...........#").is_err());
+        assert!(Burrow::from_str("#############\n#...........#\n###B#C#E#D###").is_err());
+    }
+
+    #[test]
+    fn unfolds_burrow() {
+        let burrow = Burrow::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(
+            Burrow::from_str(UNFOLDED_EXAMPLE).unwrap(),
+            burrow.unfold().unwrap()
+        );
+        assert!(burrow.unfold().unwrap().unfold().is_err());
+    }
+
+    #[test]
+    fn generates_legal_moves() {
+        let burrow = Burrow::from_str(
+            "#############
+#...B.......#
+###B#.#C#D###
+  #A#B#C#A#
+  #########",
+        )
+        .unwrap();
+        let moves = burrow.moves();
+
+        // the bronze amphipod in the hallway goes straight into its room
+        assert_eq!(1, moves.len());
+        assert_eq!(Some(Amphipod::Bronze), moves[0].0.rooms[1][0]);
+        assert_eq!(20, moves[0].1);
+
+        let burrow = Burrow::from_str(
+            "#############
+#...B.A.....#
+###B#C#.#D###
+  #A#D#C#A#
+  #########",
+        )
+        .unwrap();
+        // nobody can go home, the left room can only reach the start of the hallway, the second room
+        // is walled in, and the third room only has its own amphipod in it
+        let mut destinations: Vec<usize> = burrow
+            .moves()
+            .iter()
+            .map(|(next, _)| {
+                (0..HALLWAY_LEN)
+                    .find(|&x| next.hallway[x].is_some() && burrow.hallway[x].is_none())
+                    .unwrap()
+            })
+            .collect();
+        destinations.sort_unstable();
+        assert_eq!(vec![0, 1, 7, 9, 10], destinations);
+    }
+
+    #[test]
+    fn organized_burrow_needs_no_energy() {
+        let burrow = Burrow::from_str(
+            "#############
+#...........#
+###A#B#C#D###
+  #A#B#C#D#
+  #########",
+        )
+        .unwrap();
+
+        assert!(burrow.is_organized());
+        assert_eq!(Some(0), lowest_organizing_energy(&burrow));
+    }
+
+    #[test]
+    fn part_1() {
+        let burrow = Burrow::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(Some(12521), lowest_organizing_energy(&burrow));
+    }
+
+    #[test]
+    fn part_2() {
+        let burrow = Burrow::from_str(EXAMPLE).unwrap().unfold().unwrap();
+
+        assert_eq!(Some(44169), lowest_organizing_energy(&burrow));
+    }
 }
@@ -1,5 +1,521 @@
-use super::{Day24, DaySolution};
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day24;
+pub const META: DayMeta = DayMeta {
+    day: 24,
+    title: "Arithmetic Logic Unit",
+};
 
 impl DaySolution for Day24 {
-    // TODO
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part_1(&self, program: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(find_model_number(program, DigitPreference::Largest)?.to_string())
+    }
+
+    fn part_2(&self, program: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(find_model_number(program, DigitPreference::Smallest)?.to_string())
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+use std::str::FromStr;
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Register::W),
+            "x" => Ok(Register::X),
+            "y" => Ok(Register::Y),
+            "z" => Ok(Register::Z),
+            _ => Err(format!("Unknown register '{}'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+impl FromStr for Operand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(number) => Ok(Operand::Number(number)),
+            Err(_) => Ok(Operand::Register(Register::from_str(s)?)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        match tokens[..] {
+            ["inp", a] => Ok(Instruction::Inp(Register::from_str(a)?)),
+            [operation, a, b] => {
+                let (a, b) = (Register::from_str(a)?, Operand::from_str(b)?);
+                match operation {
+                    "add" => Ok(Instruction::Add(a, b)),
+                    "mul" => Ok(Instruction::Mul(a, b)),
+                    "div" => Ok(Instruction::Div(a, b)),
+                    "mod" => Ok(Instruction::Mod(a, b)),
+                    "eql" => Ok(Instruction::Eql(a, b)),
+                    _ => Err(format!("Unknown instruction: {}", s.trim())),
+                }
+            }
+            _ => Err(format!("Unknown instruction: {}", s.trim())),
+        }
+    }
+}
+
+use std::fmt;
+#[derive(Debug, PartialEq)]
+pub enum AluError {
+    // An `inp` instruction ran after the whole input was read
+    MissingInput,
+    DivisionByZero,
+    // `mod` is only defined for a >= 0 and b > 0
+    InvalidModulo(i64, i64),
+    // The result of an instruction does not fit in 64 bits
+    Overflow,
+}
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AluError::MissingInput => write!(f, "The program ran out of input"),
+            AluError::DivisionByZero => write!(f, "The program divided by zero"),
+            AluError::InvalidModulo(a, b) => write!(f, "The program computed {} mod {}", a, b),
+            AluError::Overflow => write!(f, "The program overflowed a register"),
+        }
+    }
+}
+impl Error for AluError {}
+
+#[derive(Debug, Default)]
+struct Alu {
+    registers: [i64; 4],
+}
+impl Alu {
+    fn register(&self, register: Register) -> i64 {
+        self.registers[register as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.register(register),
+            Operand::Number(number) => number,
+        }
+    }
+
+    // Runs a whole program from zeroed registers, reading `inp` values from the input in order
+    fn run(program: &[Instruction], input: &[i64]) -> Result<Alu, AluError> {
+        let mut alu = Alu::default();
+        let mut input = input.iter();
+
+        for &instruction in program {
+            let (a, result) = match instruction {
+                Instruction::Inp(a) => (a, *input.next().ok_or(AluError::MissingInput)?),
+                Instruction::Add(a, b) => (
+                    a,
+                    alu.register(a)
+                        .checked_add(alu.value(b))
+                        .ok_or(AluError::Overflow)?,
+                ),
+                Instruction::Mul(a, b) => (
+                    a,
+                    alu.register(a)
+                        .checked_mul(alu.value(b))
+                        .ok_or(AluError::Overflow)?,
+                ),
+                Instruction::Div(a, b) => match alu.value(b) {
+                    0 => return Err(AluError::DivisionByZero),
+                    // only i64::MIN / -1 overflows
+                    b => (a, alu.register(a).checked_div(b).ok_or(AluError::Overflow)?),
+                },
+                Instruction::Mod(a, b) => match (alu.register(a), alu.value(b)) {
+                    (a_value, b) if a_value < 0 || b <= 0 => {
+                        return Err(AluError::InvalidModulo(a_value, b))
+                    }
+                    (a_value, b) => (a, a_value % b),
+                },
+                Instruction::Eql(a, b) => (a, (alu.register(a) == alu.value(b)) as i64),
+            };
+            alu.registers[a as usize] = result;
+        }
+
+        Ok(alu)
+    }
+}
+
+fn parse_program(contents: &str) -> Result<Vec<Instruction>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Instruction::from_str)
+        .collect()
+}
+
+// MONAD
+// The program is made of one block per digit, all identical except for 3 numbers:
+//  - `div z 1` or `div z 26`
+//  - `add x a` right after it
+//  - `add y b` near the end
+// z is used as a stack of base 26 numbers. Every block with `div z 1` has a >= 10, so it can never
+// match the digit and always pushes digit + b. Every block with `div z 26` pops the last pushed
+// value, and pushes again unless its digit equals that value + a.
+// For z to end at 0, every popping block has to avoid pushing again, so each digit that pops is
+// tied to the digit that pushed: popping digit = pushing digit + b (of the push) + a (of the pop).
+const NUM_DIGITS: usize = 14;
+const BLOCK_LEN: usize = 18;
+
+fn monad_block_source(z_divisor: i64, a: i64, b: i64) -> String {
+    format!(
+        "inp w
+mul x 0
+add x z
+mod x 26
+div z {}
+add x {}
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y {}
+mul y x
+add z y",
+        z_divisor, a, b
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DigitPreference {
+    Largest,
+    Smallest,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MonadError {
+    // The block for this digit does not follow the expected structure
+    UnexpectedBlock(usize),
+    // There are not as many popping blocks as pushing blocks, so z never goes back to 0
+    UnbalancedStack,
+    NoValidModelNumber,
+    // The interpreter did not accept the model number computed from the constraints
+    Rejected(u64),
+    Crashed(AluError),
+}
+impl fmt::Display for MonadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonadError::UnexpectedBlock(digit) => {
+                write!(
+                    f,
+                    "MONAD block for digit {} has an unexpected structure",
+                    digit + 1
+                )
+            }
+            MonadError::UnbalancedStack => write!(f, "MONAD can never accept a model number"),
+            MonadError::NoValidModelNumber => write!(f, "No model number satisfies MONAD"),
+            MonadError::Rejected(model_number) => {
+                write!(
+                    f,
+                    "MONAD rejected the computed model number {}",
+                    model_number
+                )
+            }
+            MonadError::Crashed(error) => write!(f, "MONAD crashed: {}", error),
+        }
+    }
+}
+impl Error for MonadError {}
+
+// Returns the (z divisor, a, b) of every block
+fn extract_block_parameters(program: &[Instruction]) -> Result<Vec<(i64, i64, i64)>, MonadError> {
+    if program.len() != NUM_DIGITS * BLOCK_LEN {
+        return Err(MonadError::UnexpectedBlock(program.len() / BLOCK_LEN));
+    }
+
+    program
+        .chunks(BLOCK_LEN)
+        .enumerate()
+        .map(|(digit, block)| {
+            let parameters = match (block[4], block[5], block[15]) {
+                (
+                    Instruction::Div(Register::Z, Operand::Number(z_divisor)),
+                    Instruction::Add(Register::X, Operand::Number(a)),
+                    Instruction::Add(Register::Y, Operand::Number(b)),
+                ) => (z_divisor, a, b),
+                _ => return Err(MonadError::UnexpectedBlock(digit)),
+            };
+
+            let (z_divisor, a, b) = parameters;
+            let expected = parse_program(&monad_block_source(z_divisor, a, b)).unwrap();
+            let pushes_unconditionally = z_divisor == 1 && a >= 10;
+            if block != expected || !(pushes_unconditionally || z_divisor == 26) {
+                return Err(MonadError::UnexpectedBlock(digit));
+            }
+            Ok(parameters)
+        })
+        .collect()
+}
+
+fn find_model_number(
+    program: &[Instruction],
+    preference: DigitPreference,
+) -> Result<u64, MonadError> {
+    let mut digits = [0; NUM_DIGITS];
+    let mut pushed = Vec::new();
+
+    for (digit, (z_divisor, a, b)) in extract_block_parameters(program)?.into_iter().enumerate() {
+        if z_divisor == 1 {
+            pushed.push((digit, b));
+            continue;
+        }
+
+        let (push_digit, push_b) = pushed.pop().ok_or(MonadError::UnbalancedStack)?;
+        // digits[digit] = digits[push_digit] + offset, with both digits from 1 to 9
+        // (an offset too extreme to compute cannot be bridged by two digits either)
+        let offset = push_b
+            .checked_add(a)
+            .ok_or(MonadError::NoValidModelNumber)?;
+        let push_value = match preference {
+            DigitPreference::Largest => 9i64.checked_sub(offset).map(|v| v.min(9)),
+            DigitPreference::Smallest => 1i64.checked_sub(offset).map(|v| v.max(1)),
+        }
+        .ok_or(MonadError::NoValidModelNumber)?;
+        let pop_value = push_value
+            .checked_add(offset)
+            .ok_or(MonadError::NoValidModelNumber)?;
+        if !(1..=9).contains(&push_value) || !(1..=9).contains(&pop_value) {
+            return Err(MonadError::NoValidModelNumber);
+        }
+        digits[push_digit] = push_value;
+        digits[digit] = pop_value;
+    }
+    if !pushed.is_empty() {
+        return Err(MonadError::UnbalancedStack);
+    }
+
+    let model_number = digits.iter().fold(0, |n, &d| n * 10 + d as u64);
+    let alu = Alu::run(program, &digits).map_err(MonadError::Crashed)?;
+    if alu.register(Register::Z) != 0 {
+        return Err(MonadError::Rejected(model_number));
+    }
+    Ok(model_number)
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEGATE: &str = "inp x
+mul x -1";
+
+    const THREE_TIMES: &str = "inp z
+inp x
+mul z 3
+eql z x";
+
+    const BINARY: &str = "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2";
+
+    // Builds a MONAD where every pair of consecutive digits is tied by one of the offsets
+    fn monad_with_offsets(offsets: &[i64]) -> Vec<Instruction> {
+        let source: Vec<String> = offsets
+            .iter()
+            .flat_map(|offset| {
+                [
+                    monad_block_source(1, 12, 4),
+                    monad_block_source(26, offset - 4, 7),
+                ]
+            })
+            .collect();
+        parse_program(&source.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn parses_instructions() {
+        assert_eq!(
+            Instruction::Add(Register::Z, Operand::Register(Register::W)),
+            Instruction::from_str("add z w").unwrap()
+        );
+        assert_eq!(
+            Instruction::Div(Register::Y, Operand::Number(-26)),
+            Instruction::from_str("div y -26").unwrap()
+        );
+        assert!(Instruction::from_str("inp 3").is_err());
+        assert!(Instruction::from_str("sub x 1").is_err());
+        assert!(Instruction::from_str("add x").is_err());
+    }
+
+    #[test]
+    fn runs_example_programs() {
+        let negate = parse_program(NEGATE).unwrap();
+        assert_eq!(-7, Alu::run(&negate, &[7]).unwrap().register(Register::X));
+
+        let three_times = parse_program(THREE_TIMES).unwrap();
+        assert_eq!(
+            1,
+            Alu::run(&three_times, &[4, 12])
+                .unwrap()
+                .register(Register::Z)
+        );
+        assert_eq!(
+            0,
+            Alu::run(&three_times, &[4, 13])
+                .unwrap()
+                .register(Register::Z)
+        );
+
+        let binary = parse_program(BINARY).unwrap();
+        let alu = Alu::run(&binary, &[13]).unwrap();
+        assert_eq!(
+            [1, 1, 0, 1],
+            [
+                alu.register(Register::W),
+                alu.register(Register::X),
+                alu.register(Register::Y),
+                alu.register(Register::Z)
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_crashes() {
+        let program = parse_program("inp x\ninp y").unwrap();
+        assert_eq!(
+            Err(AluError::MissingInput),
+            Alu::run(&program, &[1]).map(|_| ())
+        );
+
+        let program = parse_program("inp x\ndiv y x").unwrap();
+        assert_eq!(
+            Err(AluError::DivisionByZero),
+            Alu::run(&program, &[0]).map(|_| ())
+        );
+
+        let program = parse_program("inp x\nmod x 2").unwrap();
+        assert_eq!(
+            Err(AluError::InvalidModulo(-3, 2)),
+            Alu::run(&program, &[-3]).map(|_| ())
+        );
+
+        let program = parse_program("inp x\nadd x 1").unwrap();
+        assert_eq!(
+            Err(AluError::Overflow),
+            Alu::run(&program, &[i64::MAX]).map(|_| ())
+        );
+
+        let program = parse_program("inp x\nmul x x").unwrap();
+        assert_eq!(
+            Err(AluError::Overflow),
+            Alu::run(&program, &[1 << 32]).map(|_| ())
+        );
+
+        let program = parse_program("inp x\ninp y\ndiv x y").unwrap();
+        assert_eq!(
+            Err(AluError::Overflow),
+            Alu::run(&program, &[i64::MIN, -1]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn rejects_unexpected_programs() {
+        let mut program = monad_with_offsets(&[0; 7]);
+        program[BLOCK_LEN * 3 + 6] = Instruction::Eql(Register::Y, Operand::Register(Register::W));
+        assert_eq!(
+            Err(MonadError::UnexpectedBlock(3)),
+            find_model_number(&program, DigitPreference::Largest)
+        );
+
+        let only_pushes =
+            parse_program(&vec![monad_block_source(1, 12, 4); NUM_DIGITS].join("\n")).unwrap();
+        assert_eq!(
+            Err(MonadError::UnbalancedStack),
+            find_model_number(&only_pushes, DigitPreference::Largest)
+        );
+
+        let impossible = monad_with_offsets(&[0, 0, 9, 0, 0, 0, 0]);
+        assert_eq!(
+            Err(MonadError::NoValidModelNumber),
+            find_model_number(&impossible, DigitPreference::Largest)
+        );
+
+        // push_b + a overflows, then 9 - offset and 1 - offset overflow
+        for (push_b, a) in [(i64::MAX, 1), (i64::MIN, 0)] {
+            let pair = [
+                monad_block_source(1, 12, push_b),
+                monad_block_source(26, a, 7),
+            ]
+            .join("\n");
+            let extreme = parse_program(&vec![pair; NUM_DIGITS / 2].join("\n")).unwrap();
+            for preference in [DigitPreference::Largest, DigitPreference::Smallest] {
+                assert_eq!(
+                    Err(MonadError::NoValidModelNumber),
+                    find_model_number(&extreme, preference)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn part_1() {
+        let program = monad_with_offsets(&[3, -2, 0, 8, -8, 5, -1]);
+
+        assert_eq!(
+            Ok(69979919914998),
+            find_model_number(&program, DigitPreference::Largest)
+        );
+    }
+
+    #[test]
+    fn part_2() {
+        let program = monad_with_offsets(&[3, -2, 0, 8, -8, 5, -1]);
+
+        assert_eq!(
+            Ok(14311119911621),
+            find_model_number(&program, DigitPreference::Smallest)
+        );
+    }
 }
@@ -1,5 +1,195 @@
-use super::{Day25, DaySolution};
+use super::grid::Grid;
+use super::{DayMeta, DaySolution, ParseError};
+use std::error::Error;
+
+pub struct Day25;
+pub const META: DayMeta = DayMeta {
+    day: 25,
+    title: "Sea Cucumber",
+};
 
 impl DaySolution for Day25 {
-    // TODO
+    type Input = SeaFloor;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Ok(SeaFloor::from_str(input)?)
+    }
+
+    fn part_1(&self, sea_floor: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(sea_floor.clone().find_first_still_step().to_string())
+    }
+
+    // The last day only has one puzzle
+    fn part_2(&self, _input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(String::from("Day 25 has no part 2, merry Christmas!"))
+    }
+}
+
+// Shared
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cell {
+    Empty,
+    EastFacing,
+    SouthFacing,
+}
+use std::fmt;
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::EastFacing => '>',
+            Cell::SouthFacing => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+// Sea cucumbers going past the right or bottom edge come back on the opposite side
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeaFloor {
+    cells: Grid<Cell>,
+}
+impl SeaFloor {
+    // Moves every sea cucumber of the herd whose destination was empty at the start of the phase,
+    // and returns how many moved
+    fn move_herd(&mut self, herd: Cell) -> usize {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let before = self.cells.clone();
+
+        let mut num_moved = 0;
+        for (x, y) in before.positions() {
+            if *before.get(x, y).unwrap() != herd {
+                continue;
+            }
+            let (to_x, to_y) = match herd {
+                Cell::EastFacing => ((x + 1) % width, y),
+                _ => (x, (y + 1) % height),
+            };
+            if *before.get(to_x, to_y).unwrap() == Cell::Empty {
+                *self.cells.get_mut(x, y).unwrap() = Cell::Empty;
+                *self.cells.get_mut(to_x, to_y).unwrap() = herd;
+                num_moved += 1;
+            }
+        }
+        num_moved
+    }
+
+    // The east-facing herd moves first, then the south-facing herd
+    fn step(&mut self) -> usize {
+        self.move_herd(Cell::EastFacing) + self.move_herd(Cell::SouthFacing)
+    }
+
+    fn find_first_still_step(mut self) -> usize {
+        let mut steps = 1;
+        while self.step() > 0 {
+            steps += 1;
+        }
+        steps
+    }
+}
+use std::str::FromStr;
+impl FromStr for SeaFloor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let width = rows.first().map_or(0, |r| r.len());
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            if row.len() != width {
+                return Err(format!("Sea floor row has the wrong width: {}", row));
+            }
+            for c in row.chars() {
+                cells.push(match c {
+                    '.' => Cell::Empty,
+                    '>' => Cell::EastFacing,
+                    'v' => Cell::SouthFacing,
+                    _ => return Err(format!("Unknown sea floor cell '{}'", c)),
+                });
+            }
+        }
+
+        Ok(SeaFloor {
+            cells: Grid::new(width, rows.len(), cells),
+        })
+    }
+}
+impl fmt::Display for SeaFloor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn parses_sea_floor() {
+        let sea_floor = SeaFloor::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(format!("{}\n", EXAMPLE), sea_floor.to_string());
+        assert!(SeaFloor::from_str("..>\n.").is_err());
+        assert!(SeaFloor::from_str("..<").is_err());
+    }
+
+    #[test]
+    fn moves_herds_simultaneously() {
+        let mut sea_floor = SeaFloor::from_str("...>>>>>...").unwrap();
+
+        assert_eq!(1, sea_floor.step());
+        assert_eq!("...>>>>.>..\n", sea_floor.to_string());
+        assert_eq!(2, sea_floor.step());
+        assert_eq!("...>>>.>.>.\n", sea_floor.to_string());
+    }
+
+    #[test]
+    fn wraps_around_edges() {
+        let mut sea_floor = SeaFloor::from_str(
+            "..........
+.>v....v..
+.......>..
+..........",
+        )
+        .unwrap();
+
+        sea_floor.step();
+        assert_eq!(
+            "..........
+.>........
+..v....v>.
+..........
+",
+            sea_floor.to_string()
+        );
+
+        let mut sea_floor = SeaFloor::from_str("...>\n.v..\n....\n.v..").unwrap();
+        sea_floor.step();
+        assert_eq!(">v..\n....\n.v..\n....\n", sea_floor.to_string());
+        sea_floor.step();
+        assert_eq!(">...\n.v..\n....\n.v..\n", sea_floor.to_string());
+    }
+
+    #[test]
+    fn part_1() {
+        let sea_floor = SeaFloor::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(58, sea_floor.find_first_still_step());
+    }
 }
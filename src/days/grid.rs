@@ -0,0 +1,160 @@
+// A rectangular 2D grid, shared by the days whose input is a map of cells.
+// Cells are stored row by row, and positions are (x, y) pairs with (0, 0) at the top-left corner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "Grid cells do not fill the grid"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // Up, down, left and right neighbours that are inside the grid
+    pub fn neighbours_4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    // Orthogonal and diagonal neighbours that are inside the grid
+    pub fn neighbours_8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(
+            x,
+            y,
+            &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = (x as isize, y as isize);
+
+        offsets
+            .iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(move |&(nx, ny)| nx >= 0 && ny >= 0 && nx < width && ny < height)
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+    }
+}
+impl Grid<u8> {
+    // Parses a block of lines made of single digits, like "2199943210"
+    pub fn from_digits(s: &str) -> Result<Self, String> {
+        let lines: Vec<&str> = s
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let width = lines.first().map_or(0, |l| l.len());
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in &lines {
+            if line.len() != width {
+                return Err(format!("Grid row has the wrong width: {}", line));
+            }
+            for c in line.chars() {
+                let digit = c
+                    .to_digit(10)
+                    .ok_or_else(|| format!("Bad digit '{}' in grid row: {}", c, line))?;
+                cells.push(digit as u8);
+            }
+        }
+
+        Ok(Grid::new(width, lines.len(), cells))
+    }
+}
+
+use std::fmt;
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", self.cells[y * self.width + x])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_digits() {
+        let grid = Grid::from_digits("123\n456\n").unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&6), grid.get(2, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!("123\n456\n", grid.to_string());
+        assert!(Grid::from_digits("12\n3").is_err());
+        assert!(Grid::from_digits("1a").is_err());
+    }
+
+    #[test]
+    fn finds_neighbours_inside_grid() {
+        let grid = Grid::from_digits("123\n456\n789").unwrap();
+
+        let corner: Vec<(usize, usize)> = grid.neighbours_4(0, 0).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], corner);
+        assert_eq!(4, grid.neighbours_4(1, 1).count());
+        assert_eq!(3, grid.neighbours_8(2, 2).count());
+        assert_eq!(8, grid.neighbours_8(1, 1).count());
+    }
+}
@@ -1,140 +1,257 @@
+use std::any::Any;
 use std::error::Error;
+use std::fmt;
 
-// Each day is divided into two parts.
-// For each part, the solution must read and parse the input file, and return the answer formatted as a String
+// Each day is divided into two parts, which share the same puzzle input.
+// The input is parsed once into the day's own `Input` type, then each part returns its answer formatted as a String
 // (the user should be able to just copy&paste the returned String into the input field on Advent Of Code's website)
 pub trait DaySolution {
-    fn part_1(&self, _input_file: &String) -> Result<String, Box<dyn Error>> {
-        unimplemented!();
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>>;
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>>;
+}
+
+// Implementation of ParseError as an Error
+#[derive(Debug, PartialEq)]
+pub struct ParseError(String);
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        ParseError(message)
     }
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot parse the input: {}", self.0)
+    }
+}
+impl Error for ParseError {}
 
-    fn part_2(&self, _input_file: &String) -> Result<String, Box<dyn Error>> {
-        unimplemented!();
+// An input parsed by one of the days, whose type is only known to that day
+pub struct ParsedInput(Box<dyn Any>);
+
+// Days have different input types, so the registry can only hold them through this object-safe
+// version of DaySolution, which every day gets for free
+trait Solver: Sync {
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, ParseError>;
+    fn solve_part(&self, part: u8, input: &ParsedInput) -> Result<String, Box<dyn Error>>;
+}
+impl<D> Solver for D
+where
+    D: DaySolution + Sync,
+    D::Input: 'static,
+{
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, ParseError> {
+        Ok(ParsedInput(Box::new(self.parse(input)?)))
+    }
+
+    fn solve_part(&self, part: u8, input: &ParsedInput) -> Result<String, Box<dyn Error>> {
+        let input = input
+            .0
+            .downcast_ref::<D::Input>()
+            .expect("The input was parsed by another day");
+        match part {
+            1 => self.part_1(input),
+            2 => self.part_2(input),
+            _ => panic!("Days only have parts 1 and 2, cannot solve part {}", part),
+        }
     }
 }
 
-// Each of these structures will implement their solution (DaySolution) in their own module
-pub struct Day01;
-mod day01;
+// Helpers shared between the solutions of different days
+mod grid;
 
-pub struct Day02;
-mod day02;
+// What the runner needs to know about a day, declared by each day's module next to its solution
+pub struct DayMeta {
+    pub day: i32,
+    pub title: &'static str,
+}
 
-pub struct Day03;
-mod day03;
+// A solution registered for a given day, along with its metadata
+pub struct RegisteredDay {
+    pub day: i32,
+    pub title: &'static str,
+    solution: &'static dyn Solver,
+}
+impl RegisteredDay {
+    pub fn parse(&self, input: &str) -> Result<ParsedInput, ParseError> {
+        self.solution.parse_input(input)
+    }
 
-pub struct Day04;
-mod day04;
+    // The input must come from this day's `parse`
+    pub fn solve(&self, part: u8, input: &ParsedInput) -> Result<String, Box<dyn Error>> {
+        self.solution.solve_part(part, input)
+    }
+}
 
-pub struct Day05;
+// Each day implements its solution (DaySolution) in its own module, and declares its metadata (META) there
+mod day01;
+mod day02;
+mod day03;
+mod day04;
 mod day05;
-
-pub struct Day06;
 mod day06;
-
-pub struct Day07;
 mod day07;
-
-pub struct Day08;
 mod day08;
-
-pub struct Day09;
 mod day09;
-
-pub struct Day10;
 mod day10;
-
-pub struct Day11;
 mod day11;
-
-pub struct Day12;
 mod day12;
-
-pub struct Day13;
 mod day13;
-
-pub struct Day14;
 mod day14;
-
-pub struct Day15;
 mod day15;
-
-pub struct Day16;
 mod day16;
-
-pub struct Day17;
 mod day17;
-
-pub struct Day18;
 mod day18;
-
-pub struct Day19;
 mod day19;
-
-pub struct Day20;
 mod day20;
-
-pub struct Day21;
 mod day21;
-
-pub struct Day22;
 mod day22;
-
-pub struct Day23;
 mod day23;
-
-pub struct Day24;
 mod day24;
-
-pub struct Day25;
 mod day25;
 
-// Find and return the correct solution for the given day
-pub fn get_day(day: i32) -> Result<Box<dyn DaySolution>, InvalidDay> {
-    match day {
-        1 => Ok(Box::new(Day01)),
-        2 => Ok(Box::new(Day02)),
-        3 => Ok(Box::new(Day03)),
-        4 => Ok(Box::new(Day04)),
-        5 => Ok(Box::new(Day05)),
-        6 => Ok(Box::new(Day06)),
-        7 => Ok(Box::new(Day07)),
-        8 => Ok(Box::new(Day08)),
-        9 => Ok(Box::new(Day09)),
-        10 => Ok(Box::new(Day10)),
-        11 => Ok(Box::new(Day11)),
-        12 => Ok(Box::new(Day12)),
-        13 => Ok(Box::new(Day13)),
-        14 => Ok(Box::new(Day14)),
-        15 => Ok(Box::new(Day15)),
-        16 => Ok(Box::new(Day16)),
-        17 => Ok(Box::new(Day17)),
-        18 => Ok(Box::new(Day18)),
-        19 => Ok(Box::new(Day19)),
-        20 => Ok(Box::new(Day20)),
-        21 => Ok(Box::new(Day21)),
-        22 => Ok(Box::new(Day22)),
-        23 => Ok(Box::new(Day23)),
-        24 => Ok(Box::new(Day24)),
-        25 => Ok(Box::new(Day25)),
-        _ => Err(InvalidDay(day)),
-    }
+// Registers the solution of each day along with the metadata declared by its module
+macro_rules! register_days {
+    ($($module:ident::$solution:ident,)*) => {
+        static REGISTRY: &[RegisteredDay] = &[
+            $(
+                RegisteredDay {
+                    day: $module::META.day,
+                    title: $module::META.title,
+                    solution: &$module::$solution,
+                },
+            )*
+        ];
+    };
 }
 
-// ... I am not happy with this implementation, but it does work for the small scope of Advent of Code
-// I am open to hear what others way I could implement this, in order to further learn about Rust
+register_days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+}
+
+// Every registered day, in order
+pub fn all_days() -> &'static [RegisteredDay] {
+    REGISTRY
+}
+
+// Find and return the registered solution for the given day
+pub fn get_day(day: i32) -> Result<&'static RegisteredDay, InvalidDay> {
+    if !(1..=25).contains(&day) {
+        return Err(InvalidDay::OutOfRange(day));
+    }
+
+    REGISTRY
+        .iter()
+        .find(|registered| registered.day == day)
+        .ok_or(InvalidDay::NotImplemented(day))
+}
 
 // Implementation of InvalidDay as an Error
-use std::fmt;
-#[derive(Debug)]
-pub struct InvalidDay(i32);
+#[derive(Debug, PartialEq)]
+pub enum InvalidDay {
+    OutOfRange(i32),
+    NotImplemented(i32),
+}
 impl fmt::Display for InvalidDay {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "cannot request to solve day {}, must be 1 <= day <= 25",
-            self.0
-        )
+        match self {
+            InvalidDay::OutOfRange(day) => write!(
+                f,
+                "cannot request to solve day {}, must be 1 <= day <= 25",
+                day
+            ),
+            InvalidDay::NotImplemented(day) => {
+                write!(f, "there is no solution for day {} yet", day)
+            }
+        }
     }
 }
 impl Error for InvalidDay {}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn registers_days_in_order() {
+        let days: Vec<i32> = all_days().iter().map(|registered| registered.day).collect();
+
+        assert_eq!((1..=25).collect::<Vec<i32>>(), days);
+        for registered in all_days() {
+            assert!(!registered.title.is_empty());
+        }
+    }
+
+    #[test]
+    fn finds_registered_days() {
+        let registered = get_day(16).unwrap();
+
+        assert_eq!("Packet Decoder", registered.title);
+        assert_eq!(Some(InvalidDay::OutOfRange(0)), get_day(0).err());
+        assert_eq!(Some(InvalidDay::OutOfRange(26)), get_day(26).err());
+    }
+
+    #[test]
+    fn parses_once_for_both_parts() {
+        let registered = get_day(1).unwrap();
+        let input = registered
+            .parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")
+            .unwrap();
+
+        assert_eq!("7", registered.solve(1, &input).unwrap());
+        assert_eq!("5", registered.solve(2, &input).unwrap());
+        assert!(registered.parse("199\nabc").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_inputs() {
+        for registered in all_days() {
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                registered.parse("garbage\n").map(|_| ())
+            }));
+
+            match result {
+                Ok(parsed) => assert!(parsed.is_err(), "day {} accepted garbage", registered.day),
+                Err(_) => panic!("day {} panicked while parsing", registered.day),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "parsed by another day")]
+    fn rejects_inputs_of_other_days() {
+        let input = get_day(1).unwrap().parse("199\n200").unwrap();
+
+        let _ = get_day(6).unwrap().solve(1, &input);
+    }
+}
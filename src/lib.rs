@@ -1,38 +1,312 @@
 use std::error::Error;
 
+mod bench;
 mod days;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let day = days::get_day(config.day)?;
-    println!("Solving Day {}", config.day);
+    match (config.command, config.bench) {
+        (Command::List, _) => list_days(),
+        (
+            Command::Solve {
+                day,
+                input_filename,
+            },
+            None,
+        ) => solve_day(day, &input_filename)?,
+        (
+            Command::Solve {
+                day,
+                input_filename,
+            },
+            Some(options),
+        ) => bench::run(&[(days::get_day(day)?, input_filename)], &options)?,
+        (Command::All { input_directory }, None) => solve_all_days(&input_directory)?,
+        (Command::All { input_directory }, Some(options)) => {
+            // days without an input file are left out of the benchmark
+            let inputs: Vec<_> = days::all_days()
+                .iter()
+                .map(|registered| {
+                    (
+                        registered,
+                        day_input_filename(&input_directory, registered.day),
+                    )
+                })
+                .filter(|(_, input_filename)| Path::new(input_filename).is_file())
+                .collect();
+            bench::run(&inputs, &options)?
+        }
+    }
+
+    Ok(())
+}
+
+fn list_days() {
+    for registered in days::all_days() {
+        println!("Day {:02}: {}", registered.day, registered.title);
+    }
+}
+
+use std::fs;
+fn solve_day(day: i32, input_filename: &String) -> Result<(), Box<dyn Error>> {
+    let registered = days::get_day(day)?;
+    println!("Solving Day {}: {}", registered.day, registered.title);
+
+    let input = registered.parse(&fs::read_to_string(input_filename)?)?;
+
+    for part in [1, 2] {
+        let solution = registered.solve(part, &input)?;
+        if solution.contains('\n') {
+            // start on a new line, so that answers drawn over several lines are not misaligned
+            println!("Part {}:\n{}", part, solution.trim_end_matches('\n'));
+        } else {
+            println!("Part {}: {}", part, solution);
+        }
+    }
+
+    Ok(())
+}
+
+// Runs every registered day with the input file named after it in the given directory (e.g. day07.txt),
+// and prints a table of the results, with the parsing of each input on its own row.
+// A day that fails, or even panics, does not stop the others.
+use std::path::Path;
+use std::time::Instant;
+fn solve_all_days(input_directory: &str) -> Result<(), Box<dyn Error>> {
+    let header = ["Day", "Part", "Answer", "Time", "Status"].map(String::from);
+    let mut rows = vec![header];
+    let mut multiline_answers = Vec::new();
+    let mut num_failed = 0;
+
+    with_silenced_panics(|| {
+        for registered in days::all_days() {
+            let input_filename = day_input_filename(input_directory, registered.day);
+            let input = if Path::new(&input_filename).is_file() {
+                let start = Instant::now();
+                let input = catching_panics(|| {
+                    let contents = fs::read_to_string(&input_filename)?;
+                    Ok(registered.parse(&contents)?)
+                });
+                let mut row = [
+                    format!("{:02}", registered.day),
+                    String::from("parse"),
+                    String::new(),
+                    format!("{:.2?}", start.elapsed()),
+                    String::from("ok"),
+                ];
+                if let Err(failure) = &input {
+                    row[4] = failure.clone();
+                }
+                rows.push(row);
+                Some(input)
+            } else {
+                None
+            };
+
+            for part in [1, 2] {
+                let mut row = [
+                    format!("{:02}", registered.day),
+                    part.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ];
+
+                if let Some(Ok(input)) = &input {
+                    let start = Instant::now();
+                    let result = catching_panics(|| registered.solve(part, input));
+                    row[3] = format!("{:.2?}", start.elapsed());
 
-    let p1_solution = day.part_1(&config.input_filename)?;
-    println!("Part 1: {}", p1_solution);
+                    match result {
+                        Ok(answer) => {
+                            if answer.contains('\n') {
+                                row[2] = String::from("(see below)");
+                                multiline_answers.push((registered.day, part, answer));
+                            } else {
+                                row[2] = answer;
+                            }
+                            row[4] = String::from("ok");
+                        }
+                        Err(failure) => {
+                            row[4] = failure;
+                            num_failed += 1;
+                        }
+                    }
+                } else if input.is_some() {
+                    row[4] = String::from("skipped, the input could not be parsed");
+                    num_failed += 1;
+                } else {
+                    row[4] = format!("missing {}", input_filename);
+                }
 
-    let p2_solution = day.part_2(&config.input_filename)?;
-    println!("Part 2: {}", p2_solution);
+                rows.push(row);
+            }
+        }
+    });
 
+    print_table(&rows);
+    for (day, part, answer) in multiline_answers {
+        println!();
+        println!(
+            "Day {:02} part {}:\n{}",
+            day,
+            part,
+            answer.trim_end_matches('\n')
+        );
+    }
+
+    if num_failed > 0 {
+        return Err(Box::new(FailedParts(num_failed)));
+    }
     Ok(())
 }
 
+fn day_input_filename(input_directory: &str, day: i32) -> String {
+    Path::new(input_directory)
+        .join(format!("day{:02}.txt", day))
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Runs a phase of a solution (parsing or solving), turning both errors and panics into a description of the failure
+use std::panic::{self, AssertUnwindSafe};
+fn catching_panics<T>(phase: impl FnOnce() -> Result<T, Box<dyn Error>>) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(phase)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(format!("error: {}", e)),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+// Panic messages are reported along with the results, instead of being printed when they happen
+fn with_silenced_panics<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(default_hook);
+    result
+}
+
+// Prints the rows with aligned columns, the first row being the header
+fn print_table<const N: usize>(rows: &[[String; N]]) {
+    print!("{}", format_table(rows));
+}
+
+fn format_table<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut table = String::new();
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join(" | ").trim_end());
+        table.push('\n');
+
+        if i == 0 {
+            let separators: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            table.push_str(&separators.join("-+-"));
+            table.push('\n');
+        }
+    }
+
+    table
+}
+
 pub struct Config {
-    day: i32,
-    input_filename: String,
+    command: Command,
+    // Benchmark the solutions instead of just printing their answers
+    bench: Option<bench::BenchOptions>,
+}
+
+enum Command {
+    // List every day with a solution
+    List,
+    Solve { day: i32, input_filename: String },
+    // Solve every registered day, with the inputs found in a directory
+    All { input_directory: String },
 }
 
 impl Config {
     pub fn new(args: &[String]) -> Result<Config, Box<dyn Error>> {
-        if args.len() < 3 {
-            return Err(Box::new(NotEnoughArguments));
+        let mut positional = Vec::new();
+        let mut bench = false;
+        let mut iterations = None;
+        let mut json_output = None;
+
+        let mut args_iter = args.iter().skip(1);
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "--bench" => bench = true,
+                "--iterations" => {
+                    let value: usize = args_iter.next().ok_or(NotEnoughArguments)?.parse()?;
+                    if value == 0 {
+                        return Err(Box::new(InvalidArgument("--iterations must be at least 1")));
+                    }
+                    iterations = Some(value);
+                }
+                "--json" => json_output = Some(args_iter.next().ok_or(NotEnoughArguments)?.clone()),
+                flag if flag.starts_with("--") => {
+                    return Err(Box::new(InvalidArgument("unknown option")))
+                }
+                _ => positional.push(arg),
+            }
         }
 
-        let day: i32 = args[1].parse()?;
-        let input_filename = args[2].clone();
+        let bench = match (bench, iterations, json_output) {
+            (true, iterations, json_output) => Some(bench::BenchOptions {
+                iterations: iterations.unwrap_or(bench::DEFAULT_ITERATIONS),
+                json_output,
+            }),
+            (false, None, None) => None,
+            (false, _, _) => {
+                return Err(Box::new(InvalidArgument(
+                    "--iterations and --json can only be used with --bench",
+                )))
+            }
+        };
 
-        Ok(Config {
-            day,
-            input_filename,
-        })
+        let command = match positional[..] {
+            [] => return Err(Box::new(NotEnoughArguments)),
+            [command] if command == "list" => {
+                if bench.is_some() {
+                    return Err(Box::new(InvalidArgument(
+                        "cannot benchmark the list of days",
+                    )));
+                }
+                Command::List
+            }
+            [_] => return Err(Box::new(NotEnoughArguments)),
+            [command, ..] if command == "list" => {
+                return Err(Box::new(InvalidArgument("too many arguments")))
+            }
+            [command, input_directory] if command == "all" => Command::All {
+                input_directory: input_directory.clone(),
+            },
+            [day, input_filename] => Command::Solve {
+                day: day.parse()?,
+                input_filename: input_filename.clone(),
+            },
+            _ => return Err(Box::new(InvalidArgument("too many arguments"))),
+        };
+
+        Ok(Config { command, bench })
     }
 }
 
@@ -46,3 +320,121 @@ impl fmt::Display for NotEnoughArguments {
     }
 }
 impl Error for NotEnoughArguments {}
+
+// Implementation of FailedParts as an Error
+#[derive(Debug)]
+struct FailedParts(usize);
+impl fmt::Display for FailedParts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part(s) failed", self.0)
+    }
+}
+impl Error for FailedParts {}
+
+// Implementation of InvalidArgument as an Error
+#[derive(Debug)]
+struct InvalidArgument(&'static str);
+impl fmt::Display for InvalidArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid argument: {}", self.0)
+    }
+}
+impl Error for InvalidArgument {}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(args: &[&str]) -> Result<Config, Box<dyn Error>> {
+        let args: Vec<String> = ["advent_of_code_2021"]
+            .iter()
+            .chain(args)
+            .map(|a| a.to_string())
+            .collect();
+        Config::new(&args)
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert!(matches!(config(&["list"]).unwrap().command, Command::List));
+        assert!(matches!(
+            config(&["7", "input/day07.txt"]).unwrap().command,
+            Command::Solve { day: 7, .. }
+        ));
+        let bench = config(&["all", "input", "--bench", "--iterations", "3"])
+            .unwrap()
+            .bench
+            .unwrap();
+        assert_eq!(3, bench.iterations);
+        assert!(config(&["7"]).is_err());
+        assert!(config(&["list", "--json", "out.json"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        let err = config(&["all", "input", "--bench", "--iteration", "5"]).err();
+        assert_eq!(
+            Some(String::from("invalid argument: unknown option")),
+            err.map(|e| e.to_string())
+        );
+        assert!(config(&["all", "input", "--foo"]).is_err());
+    }
+
+    #[test]
+    fn rejects_extra_arguments() {
+        let err = config(&["all", "./input", "extra"]).err();
+        assert_eq!(
+            Some(String::from("invalid argument: too many arguments")),
+            err.map(|e| e.to_string())
+        );
+        assert!(config(&["7", "input/day07.txt", "extra"]).is_err());
+        assert_eq!(
+            Some(String::from("invalid argument: too many arguments")),
+            config(&["list", "extra"]).err().map(|e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn describes_failed_phases() {
+        assert_eq!(Ok(42), catching_panics(|| Ok(42)));
+        assert_eq!(
+            Err(String::from("error: not enough arguments")),
+            catching_panics::<()>(|| Err(Box::new(NotEnoughArguments)))
+        );
+        assert_eq!(
+            Err(String::from("panicked: static message")),
+            catching_panics::<()>(|| panic!("static message"))
+        );
+        assert_eq!(
+            Err(String::from("panicked: formatted message 7")),
+            catching_panics::<()>(|| panic!("formatted message {}", 7))
+        );
+    }
+
+    #[test]
+    fn names_input_files_after_days() {
+        let expected = Path::new("input").join("day07.txt");
+
+        assert_eq!(expected.to_string_lossy(), day_input_filename("input", 7));
+        assert!(day_input_filename("input", 25).ends_with("day25.txt"));
+    }
+
+    #[test]
+    fn aligns_table_columns() {
+        let rows = [
+            ["Day", "Part", "Answer"].map(String::from),
+            ["01", "1", "1167"].map(String::from),
+            ["13", "2", ""].map(String::from),
+        ];
+
+        assert_eq!(
+            "Day | Part | Answer
+----+------+-------
+01  | 1    | 1167
+13  | 2    |
+",
+            format_table(&rows)
+        );
+    }
+}
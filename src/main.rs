@@ -4,11 +4,26 @@ use std::{env, process};
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    let config = Config::new(&args).unwrap_or_else(|_err| {
-        println!("Usage: advent_of_code_2021 <DAY> <FILE>");
-        println!("Run the solution for Advent of Code 2021 DAY, using FILE contents as input.");
+    let config = Config::new(&args).unwrap_or_else(|err| {
+        println!("Error: {}", err);
+        println!();
+        println!(
+            "Usage: advent_of_code_2021 <DAY> <FILE> [--bench [--iterations N] [--json OUTPUT]]"
+        );
+        println!(
+            "       advent_of_code_2021 all <DIRECTORY> [--bench [--iterations N] [--json OUTPUT]]"
+        );
+        println!("       advent_of_code_2021 list");
+        println!("Run the solution for Advent of Code 2021 DAY, using FILE contents as input,");
+        println!("run every day using the dayNN.txt files in DIRECTORY as inputs,");
+        println!("or list the days that have a solution.");
+        println!(
+            "With --bench, each part is run N times (10 by default) and timing statistics are"
+        );
+        println!("printed instead, and also written as JSON to OUTPUT if given.");
         println!("Example: advent_of_code_2021 1 ./input/day01.txt");
-        println!("");
+        println!("         advent_of_code_2021 all ./input --bench --json bench.json");
+        println!();
         println!("Check https://adventofcode.com/2021/ to learn more about Advent of Code 2021!");
 
         process::exit(1);